@@ -0,0 +1,67 @@
+//! An example for Linux desktops reading a real keyboard key or gamepad button through `evdev`,
+//! handy to tune gestures without any hardware. Usage: `cargo run -- /dev/input/event0 57`,
+//! where 57 is the space key code. Reading input devices usually requires the `input` group or root.
+//!
+//! Required features: `std`.
+use std::{
+    env, io,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use button_driver::{Button, ButtonConfig, Mode, PinWrapper};
+use evdev::{Device, Key};
+
+/// A key of an input device, high while pressed.
+struct KeyPin {
+    device: Device,
+    key: Key,
+}
+
+impl PinWrapper for KeyPin {
+    fn is_high(&mut self) -> bool {
+        self.device
+            .get_key_state()
+            .map(|keys| keys.contains(self.key))
+            .unwrap_or_default()
+    }
+}
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+    let path = args.next().unwrap_or_else(|| "/dev/input/event0".into());
+    let key = args
+        .next()
+        .and_then(|code| code.parse().ok())
+        .map_or(Key::KEY_SPACE, Key::new);
+
+    let device = Device::open(path)?;
+    println!(
+        "Listening to {key:?} of {}",
+        device.name().unwrap_or("unknown device")
+    );
+
+    let config = ButtonConfig {
+        mode: Mode::PullDown,
+        ..Default::default()
+    };
+    let mut button = Button::<_, Instant>::new(KeyPin { device, key }, config);
+
+    loop {
+        button.tick();
+
+        if button.is_clicked() {
+            println!("Click");
+        } else if button.is_double_clicked() {
+            println!("Double click");
+        } else if button.is_triple_clicked() {
+            println!("Triple click");
+        } else if let Some(dur) = button.held_time() {
+            println!("Held for {dur:?}");
+        }
+
+        button.reset();
+        // Every read is a syscall, do not spin at full speed.
+        sleep(Duration::from_millis(1));
+    }
+}
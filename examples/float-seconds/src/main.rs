@@ -0,0 +1,51 @@
+//! An example for hosted simulations measuring time in `f64` seconds instead of `Duration`.
+//! The simulation advances its own clock, the button pin is simulated by a scripted pattern.
+//!
+//! Required features: none.
+use std::{cell::Cell, rc::Rc};
+
+use button_driver::{Button, ButtonConfig, ClockSource, FnPin, Mode};
+
+/// Simulation step in seconds.
+const STEP: f64 = 0.005;
+
+/// The simulation time in seconds.
+#[derive(Clone, Default)]
+struct SimTime(Rc<Cell<f64>>);
+
+impl ClockSource<f64, f64> for SimTime {
+    fn now(&self) -> f64 {
+        self.0.get()
+    }
+}
+
+/// Pin levels over time: a double click followed by a hold.
+fn pin_level(time: f64) -> bool {
+    (0.1..0.2).contains(&time) || (0.3..0.4).contains(&time) || (1.0..2.2).contains(&time)
+}
+
+fn main() {
+    let time = SimTime::default();
+    let config = ButtonConfig::new(0.02, 0.15, 0.5, Mode::PullDown);
+    let pin_time = time.clone();
+    let mut button = Button::with_clock(
+        FnPin(move || pin_level(pin_time.now())),
+        config,
+        time.clone(),
+    );
+
+    for step in 0..600 {
+        time.0.set(f64::from(step) * STEP);
+        button.tick();
+
+        if button.is_clicked() {
+            println!("Click");
+        } else if button.is_double_clicked() {
+            println!("Double click");
+        } else if let Some(held) = button.held_time() {
+            println!("Held for {held:.2}s");
+        }
+
+        button.reset();
+    }
+}
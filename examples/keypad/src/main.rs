@@ -0,0 +1,147 @@
+//! An example scanning a 4x4 matrix keypad: rows are driven high one at a time and columns are read back,
+//! each intersection is a [PortPin](button_driver::PortPin) of a [BankButtons] with 16 buttons.
+//! The rows and columns are simulated by a scripted key pattern, replace them with your HAL pins.
+//!
+//! Required features: `std`, `embedded_hal`.
+use std::{
+    array,
+    cell::Cell,
+    convert::Infallible,
+    rc::Rc,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use button_driver::{BankButtons, ButtonConfig, Mode, PortWrapper};
+use embedded_hal::digital::{ErrorType, InputPin, OutputPin};
+
+/// Key labels, indexed by `row * 4 + column`.
+const KEYS: [char; 16] = [
+    '1', '2', '3', 'A', '4', '5', '6', 'B', '7', '8', '9', 'C', '*', '0', '#', 'D',
+];
+
+/// A keypad scanned row by row, bit `row * 4 + column` of the port is high while the key is pressed.
+struct Keypad<R, C> {
+    rows: [R; 4],
+    columns: [C; 4],
+}
+
+impl<R: OutputPin, C: InputPin> PortWrapper for Keypad<R, C> {
+    fn read(&mut self) -> u32 {
+        let mut port = 0;
+
+        for (row, row_pin) in self.rows.iter_mut().enumerate() {
+            // Real hardware may need a few microseconds for the columns to settle after this.
+            row_pin.set_high().ok();
+            for (column, column_pin) in self.columns.iter_mut().enumerate() {
+                if column_pin.is_high().unwrap_or_default() {
+                    port |= 1 << (row * 4 + column);
+                }
+            }
+            row_pin.set_low().ok();
+        }
+
+        port
+    }
+}
+
+/// The row currently driven high, shared by the simulated pins.
+type Bus = Rc<Cell<Option<usize>>>;
+
+/// Simulated row output.
+struct RowPin {
+    row: usize,
+    bus: Bus,
+}
+
+impl ErrorType for RowPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for RowPin {
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.bus.set(Some(self.row));
+        Ok(())
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.bus.set(None);
+        Ok(())
+    }
+}
+
+/// Simulated column input, high if the pressed key is in the driven row.
+struct ColumnPin {
+    column: usize,
+    bus: Bus,
+    start: Instant,
+}
+
+impl ErrorType for ColumnPin {
+    type Error = Infallible;
+}
+
+impl InputPin for ColumnPin {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        let key = pressed_key(self.start.elapsed());
+        Ok(self
+            .bus
+            .get()
+            .is_some_and(|row| key == Some(row * 4 + self.column)))
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+/// The pressed key over time: a double click on `5` followed by a hold of `#`.
+fn pressed_key(time: Duration) -> Option<usize> {
+    match time.as_millis() {
+        100..200 | 300..400 => Some(5),
+        800..1600 => Some(14),
+        _ => None,
+    }
+}
+
+fn main() {
+    let config = ButtonConfig {
+        mode: Mode::PullDown,
+        ..ButtonConfig::default()
+    };
+    let bus = Bus::default();
+    let start = Instant::now();
+    let keypad = Keypad {
+        rows: array::from_fn(|row| RowPin {
+            row,
+            bus: bus.clone(),
+        }),
+        columns: array::from_fn(|column| ColumnPin {
+            column,
+            bus: bus.clone(),
+            start,
+        }),
+    };
+    let mut keys = BankButtons::<_, Instant, 16>::new(keypad, config);
+
+    while start.elapsed() < Duration::from_secs(2) {
+        keys.tick();
+
+        for (key, button) in keys.buttons.iter_mut().enumerate() {
+            if button.long_press_triggered() {
+                println!("{} holding...", KEYS[key]);
+            }
+            let clicks = button.clicks();
+            if clicks > 0 {
+                println!("{} clicked {clicks} time(s)", KEYS[key]);
+            }
+            if let Some(held) = button.held_time() {
+                println!("{} held for {held:?}", KEYS[key]);
+            }
+
+            button.reset();
+        }
+
+        sleep(Duration::from_millis(5));
+    }
+}
@@ -0,0 +1,38 @@
+//! An example for Linux single board computers (e.g. Raspberry Pi) reading a button through
+//! the GPIO character device. Usage: `cargo run -- /dev/gpiochip0 17`.
+//!
+//! Required features: `gpio_cdev`.
+use std::{env, time::Instant};
+
+use button_driver::{Button, ButtonConfig, CdevPin};
+use gpio_cdev::{Chip, LineRequestFlags};
+
+fn main() -> Result<(), gpio_cdev::Error> {
+    let mut args = env::args().skip(1);
+    let chip = args.next().unwrap_or_else(|| "/dev/gpiochip0".into());
+    let line = args.next().and_then(|l| l.parse().ok()).unwrap_or(17);
+
+    let handle = Chip::new(chip)?
+        .get_line(line)?
+        .request(LineRequestFlags::INPUT, 0, "button-driver")?;
+
+    let mut button = Button::<_, Instant>::new(CdevPin(handle), ButtonConfig::default());
+
+    loop {
+        button.tick();
+
+        if button.is_clicked() {
+            println!("Click");
+        } else if button.is_double_clicked() {
+            println!("Double click");
+        } else if button.is_triple_clicked() {
+            println!("Triple click");
+        } else if let Some(dur) = button.current_holding_time() {
+            println!("Held for {dur:?}");
+        } else if let Some(dur) = button.held_time() {
+            println!("Total holding time {dur:?}");
+        }
+
+        button.reset();
+    }
+}
@@ -0,0 +1,109 @@
+//! An example of how to use an RTIC 2 monotonic as the `Button` time source.
+//!
+//! The monotonic instant is wrapped into a local type implementing `InstantProvider`,
+//! the same works for any other `rtic_monotonics` monotonic.
+//!
+//! Required features: `embedded_hal_old` or `embedded_hal` with some modifications.
+#![no_std]
+#![no_main]
+
+use core::{ops::Sub, time::Duration};
+
+use button_driver::InstantProvider;
+use panic_halt as _;
+use rtic_monotonics::systick::prelude::*;
+
+systick_monotonic!(Mono, 1_000);
+
+/// The monotonic instant with 1 ms resolution.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+struct Instant(<Mono as Monotonic>::Instant);
+
+impl Sub<Instant> for Instant {
+    type Output = Duration;
+
+    fn sub(self, rhs: Instant) -> Self::Output {
+        self.0
+            .checked_duration_since(rhs.0)
+            .map_or(Duration::ZERO, |dur| Duration::from_millis(dur.to_millis()))
+    }
+}
+
+impl InstantProvider<Duration> for Instant {
+    fn now() -> Self {
+        Instant(Mono::now())
+    }
+}
+
+#[rtic::app(device = stm32f1xx_hal::pac, dispatchers = [USART1])]
+mod app {
+    use super::*;
+
+    use button_driver::{Button, ButtonConfig, Mode};
+    use rtt_target::{rprintln, rtt_init_print};
+    use stm32f1xx_hal::{
+        gpio::{Input, Output, PullDown, PushPull, PC13, PC15},
+        prelude::*,
+    };
+
+    #[shared]
+    struct Shared {}
+
+    #[local]
+    struct Local {
+        button: Button<PC15<Input<PullDown>>, Instant>,
+        led: PC13<Output<PushPull>>,
+    }
+
+    #[init]
+    fn init(cx: init::Context) -> (Shared, Local) {
+        rtt_init_print!();
+        let mut flash = cx.device.FLASH.constrain();
+        let rcc = cx.device.RCC.constrain();
+        let clocks = rcc.cfgr.freeze(&mut flash.acr);
+
+        Mono::start(cx.core.SYST, clocks.sysclk().to_Hz());
+
+        let mut gpioc = cx.device.GPIOC.split();
+        let led = gpioc.pc13.into_push_pull_output(&mut gpioc.crh);
+        let button_pin = gpioc.pc15.into_pull_down_input(&mut gpioc.crh);
+
+        let config = ButtonConfig {
+            mode: Mode::PullDown,
+            ..Default::default()
+        };
+        let button = Button::new(button_pin, config);
+
+        poll::spawn().ok();
+
+        (Shared {}, Local { button, led })
+    }
+
+    #[task(local = [button, led])]
+    async fn poll(cx: poll::Context) {
+        let button = cx.local.button;
+        let led = cx.local.led;
+
+        loop {
+            button.tick();
+
+            if button.is_clicked() {
+                led.set_low();
+                rprintln!("Click");
+            } else if button.is_double_clicked() {
+                led.set_high();
+                rprintln!("Double click");
+            } else if button.is_triple_clicked() {
+                rprintln!("Triple click");
+            } else if let Some(dur) = button.current_holding_time() {
+                rprintln!("Held for {:?}", dur);
+            } else if let Some(dur) = button.held_time() {
+                rprintln!("Total holding time {:?}", dur);
+            }
+
+            button.reset();
+
+            Mono::delay(1.millis()).await;
+        }
+    }
+}
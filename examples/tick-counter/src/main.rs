@@ -0,0 +1,37 @@
+//! An example for systems without any timer: the time is measured in main loop iterations.
+//! The button pin is simulated by a scripted pattern, replace it with your GPIO read.
+//!
+//! Required features: none.
+use button_driver::{Button, ButtonConfig, FnPin, InstantProvider, Mode, TickCount};
+
+/// Pin levels per loop iteration: a double click followed by a hold.
+fn pin_level(tick: u32) -> bool {
+    matches!(tick, 10..=40 | 100..=130 | 400..=1200)
+}
+
+fn main() {
+    let config = ButtonConfig {
+        mode: Mode::PullDown,
+        // One loop iteration is assumed to take a millisecond.
+        ..ButtonConfig::<u32>::default()
+    };
+    let mut button =
+        Button::<_, TickCount, u32>::new(FnPin(|| pin_level(TickCount::now().0)), config);
+
+    for _ in 0..2000 {
+        TickCount::increment();
+        button.tick();
+
+        if button.is_clicked() {
+            println!("Click");
+        } else if button.is_double_clicked() {
+            println!("Double click");
+        } else if button.is_triple_clicked() {
+            println!("Triple click");
+        } else if let Some(ticks) = button.held_time() {
+            println!("Held for {ticks} ticks");
+        }
+
+        button.reset();
+    }
+}
@@ -0,0 +1,37 @@
+//! An example measuring holds in ticks of a fixed-rate loop, without any `Duration` math.
+//! The loop runs at 100Hz, so a tick is 10ms. The button pin is simulated by a scripted pattern,
+//! replace it with your GPIO read.
+//!
+//! Required features: none.
+use std::{thread::sleep, time::Duration};
+
+use button_driver::{Button, ButtonConfig, FnPin, InstantProvider, Mode, TickCount};
+
+/// Loop rate in Hz.
+const RATE: u32 = 100;
+
+/// Pin levels per loop iteration: a short hold followed by a long one.
+fn pin_level(tick: u32) -> bool {
+    matches!(tick, 10..=80 | 150..=400)
+}
+
+fn main() {
+    let config = ButtonConfig::<u32>::new(1, RATE * 15 / 100, RATE / 2, Mode::PullDown);
+    let mut button =
+        Button::<_, TickCount, u32>::new(FnPin(|| pin_level(TickCount::now().0)), config);
+
+    for _ in 0..500 {
+        TickCount::increment();
+        button.tick();
+
+        if button.long_press_triggered() {
+            println!("Holding...");
+        }
+        if let Some(ticks) = button.held_time() {
+            println!("Held for {ticks} ticks, {}ms", ticks * 1000 / RATE);
+        }
+
+        button.reset();
+        sleep(Duration::from_millis((1000 / RATE).into()));
+    }
+}
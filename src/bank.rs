@@ -0,0 +1,65 @@
+use core::{ops::Sub, time::Duration};
+
+use crate::{
+    Button, ButtonConfig, ClockSource, GlobalClock, InstantProvider, PortPin, PortWrapper,
+};
+
+/// A bank of buttons connected to a single port, e.g. an MCP23017 or PCF8574 port expander.
+///
+/// The port is read once per [tick](Self::tick) for all buttons,
+/// button `n` is connected to the pin `n` of the port.
+#[derive(Clone, Debug)]
+pub struct BankButtons<W, I, const N: usize, D = Duration, C = GlobalClock> {
+    /// An inner port.
+    pub port: W,
+    /// Buttons of the bank, indexed by their pin number.
+    pub buttons: [Button<PortPin, I, D, C>; N],
+}
+
+impl<W, I, const N: usize, D> BankButtons<W, I, N, D>
+where
+    W: PortWrapper,
+    I: InstantProvider<D> + PartialOrd,
+    D: Clone + PartialOrd + Default,
+{
+    /// Creates a new [BankButtons] with the same config for every button.
+    ///
+    /// # Panics
+    /// If `N` exceeds the 32 bits of a port.
+    pub fn new(port: W, config: ButtonConfig<D>) -> Self {
+        Self::with_clock(port, config, GlobalClock)
+    }
+}
+
+impl<W, I, const N: usize, D, C> BankButtons<W, I, N, D, C>
+where
+    W: PortWrapper,
+    I: Sub<I, Output = D> + Clone + PartialOrd,
+    D: Clone + PartialOrd + Default,
+    C: ClockSource<I, D> + Clone,
+{
+    /// Creates a new [BankButtons] with the same config and [ClockSource] for every button.
+    ///
+    /// # Panics
+    /// If `N` exceeds the 32 bits of a port.
+    pub fn with_clock(port: W, config: ButtonConfig<D>, clock: C) -> Self {
+        assert!(N <= 32, "a port has at most 32 pins");
+
+        Self {
+            port,
+            buttons: core::array::from_fn(|bit| {
+                Button::with_clock(PortPin::new(bit as u8), config.clone(), clock.clone())
+            }),
+        }
+    }
+
+    /// Reads the port once and updates all buttons.
+    pub fn tick(&mut self) {
+        let port = self.port.read();
+
+        for button in &mut self.buttons {
+            button.pin.update(port);
+            button.tick();
+        }
+    }
+}
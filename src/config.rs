@@ -1,4 +1,7 @@
-use core::time::Duration;
+use core::{
+    ops::{Div, Mul},
+    time::Duration,
+};
 
 /// Default debounce time for a button.
 pub const DEFAULT_DEBOUNCE: Duration = Duration::from_micros(900);
@@ -7,8 +10,22 @@ pub const DEFAULT_RELEASE: Duration = Duration::from_millis(150);
 /// Default hold time for a button.
 pub const DEFAULT_HOLD: Duration = Duration::from_millis(500);
 
+// Evaluated at compile time, so integer configs do not pull in `Duration` arithmetic.
+// `as` is safe here because these contacts won't exceed `u32` limit
+/// Default debounce time in milliseconds, at least one tick.
+pub const DEFAULT_DEBOUNCE_MS: u32 = if DEFAULT_DEBOUNCE.as_millis() == 0 {
+    1
+} else {
+    DEFAULT_DEBOUNCE.as_millis() as u32
+};
+/// Default release time in milliseconds.
+pub const DEFAULT_RELEASE_MS: u32 = DEFAULT_RELEASE.as_millis() as u32;
+/// Default hold time in milliseconds.
+pub const DEFAULT_HOLD_MS: u32 = DEFAULT_HOLD.as_millis() as u32;
+
 /// Various [Button] parameters.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ButtonConfig<D = Duration> {
     /// How much time the button should be pressed to in order to count it as a press.
     pub debounce: D,
@@ -16,6 +33,50 @@ pub struct ButtonConfig<D = Duration> {
     pub release: D,
     /// How much time the button should be pressed to be held.
     pub hold: D,
+    /// How much time the button should be released to finish a press or a hold.
+    /// [None] means the release edge is not debounced.
+    ///
+    /// Together with [debounce](Self::debounce) it allows switches bouncing differently on each edge.
+    pub release_debounce: Option<D>,
+    /// Minimal total press length, shorter presses are discarded without counting a click or producing an event.
+    /// The press is confirmed, see [ButtonEvent::Press](crate::ButtonEvent::Press), once it lasts both this and [debounce](Self::debounce).
+    /// [None] means any debounced press is counted.
+    pub min_press: Option<D>,
+    /// Maximal amount of clicks counted in a streak, further clicks extend the streak without being counted.
+    /// [None] means no limit.
+    pub max_clicks: Option<usize>,
+    /// What happens to the clicks counted before a hold in the same streak.
+    pub click_hold_policy: ClickHoldPolicy,
+    /// Automatically [reset](crate::Button::reset) the finished gesture on the next tick,
+    /// so accessors like [is_clicked](crate::Button::is_clicked) are true for a single tick only.
+    pub auto_reset: bool,
+    /// Auto-repeat interval while the button is held, see [repeats](crate::Button::repeats).
+    /// [None] disables auto-repeat.
+    pub repeat: Option<D>,
+    /// Shortens the [repeat](Self::repeat) interval to the given floor after the given amount of repeats in a hold,
+    /// like the OS key repeat acceleration. [None] keeps the interval constant.
+    pub repeat_accel: Option<(usize, D)>,
+    /// Ignore a press already in progress when the button leaves [Unknown](crate::State::Unknown), e.g. held during power-on:
+    /// the button stays [Unknown](crate::State::Unknown) until released, without counting a click.
+    pub ignore_initial_press: bool,
+    /// A dead zone between a click and a hold: the button still enters [Held](crate::State::Held) after [hold](Self::hold),
+    /// but the press is counted as a hold, with [HoldStart](crate::ButtonEvent::HoldStart)
+    /// and [long_press_triggered](crate::Button::long_press_triggered), only once a tick sees it exceed this time.
+    /// Released earlier, it is a click and produces [Release](crate::ButtonEvent::Release). [None] means `hold` alone decides.
+    pub hold_must_exceed: Option<D>,
+    /// Maximal amount of clicks reported by [clicks](crate::Button::clicks), further clicks are still counted
+    /// but reported as this amount, e.g. so accidental extra clicks collapse to a triple click. [None] means no limit.
+    pub click_ceiling: Option<usize>,
+    /// Maximal time between two ticks, a longer gap resets the button to [Unknown](crate::State::Unknown)
+    /// instead of acting on the stale state, e.g. after the loop was blocked. [None] means no limit.
+    pub max_tick_gap: Option<D>,
+    /// Autofire interval while the button is pressed, see [fire](crate::Button::fire).
+    /// [None] disables autofire.
+    pub autofire_interval: Option<D>,
+    /// Minimal time from the release of a click to the next press, e.g. against double triggers of a sensitive touch button.
+    /// Presses within it are ignored, a press still down when it ends is counted from then on.
+    /// Unlike [debounce](Self::debounce) it rate-limits whole clicks. [None] disables it.
+    pub min_click_interval: Option<D>,
     /// Button direction.
     pub mode: Mode,
 }
@@ -29,7 +90,97 @@ impl<D> ButtonConfig<D> {
             debounce,
             release,
             hold,
+            release_debounce: None,
+            min_press: None,
+            max_clicks: None,
+            click_hold_policy: ClickHoldPolicy::HoldPreservesClicks,
+            auto_reset: false,
+            repeat: None,
+            repeat_accel: None,
+            ignore_initial_press: false,
+            hold_must_exceed: None,
+            click_ceiling: None,
+            max_tick_gap: None,
+            autofire_interval: None,
+            min_click_interval: None,
+            mode,
+        }
+    }
+}
+
+impl<D> ButtonConfig<D> {
+    /// Returns new [ButtonConfig] with the timings derived from `release`:
+    /// `debounce` is `release / debounce_ratio` and `hold` is `release * hold_ratio`.
+    ///
+    /// E.g. `ButtonConfig::scaled(Duration::from_millis(150), 150, 3, mode)` gives 1ms debounce and 450ms hold.
+    ///
+    /// # Panics
+    /// If `debounce_ratio` is zero.
+    pub fn scaled(release: D, debounce_ratio: u32, hold_ratio: u32, mode: Mode) -> Self
+    where
+        D: Clone + Div<u32, Output = D> + Mul<u32, Output = D>,
+    {
+        Self::new(
+            release.clone() / debounce_ratio,
+            release.clone(),
+            release * hold_ratio,
             mode,
+        )
+    }
+
+    /// Converts every duration of the config with `f`, e.g. between `embassy_time::Duration` and [Duration].
+    ///
+    /// The conversion may lose precision, e.g. [Duration] to milliseconds truncates the microseconds,
+    /// so a short `debounce` can become zero.
+    pub fn map_duration<D2>(self, mut f: impl FnMut(D) -> D2) -> ButtonConfig<D2> {
+        ButtonConfig {
+            debounce: f(self.debounce),
+            release: f(self.release),
+            hold: f(self.hold),
+            release_debounce: self.release_debounce.map(&mut f),
+            min_press: self.min_press.map(&mut f),
+            max_clicks: self.max_clicks,
+            click_hold_policy: self.click_hold_policy,
+            auto_reset: self.auto_reset,
+            repeat: self.repeat.map(&mut f),
+            repeat_accel: self.repeat_accel.map(|(after, floor)| (after, f(floor))),
+            ignore_initial_press: self.ignore_initial_press,
+            hold_must_exceed: self.hold_must_exceed.map(&mut f),
+            click_ceiling: self.click_ceiling,
+            max_tick_gap: self.max_tick_gap.map(&mut f),
+            autofire_interval: self.autofire_interval.map(&mut f),
+            min_click_interval: self.min_click_interval.map(&mut f),
+            mode: self.mode,
+        }
+    }
+}
+
+impl<D> ButtonConfig<D>
+where
+    Self: From<SwitchType>,
+{
+    /// Returns the default [ButtonConfig] tuned for the given [SwitchType], see it for the chosen timings.
+    ///
+    /// Presets exist for [Duration] and, with the `embassy` feature, `embassy_time::Duration`.
+    /// With both available, pick one with `ButtonConfig::<Duration>::for_switch`.
+    pub fn for_switch(switch: SwitchType) -> Self {
+        switch.into()
+    }
+}
+
+impl<D: PartialOrd + Default> ButtonConfig<D> {
+    /// Checks that `release` and `hold` are not zero, `debounce` is not greater than `release`
+    /// and `release` is not greater than `hold`.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let zero = D::default();
+        if self.release == zero || self.hold == zero {
+            Err(ConfigError::ZeroDuration)
+        } else if self.debounce > self.release {
+            Err(ConfigError::DebounceExceedsRelease)
+        } else if self.release > self.hold {
+            Err(ConfigError::ReleaseExceedsHold)
+        } else {
+            Ok(())
         }
     }
 }
@@ -40,6 +191,56 @@ impl Default for ButtonConfig<Duration> {
             debounce: DEFAULT_DEBOUNCE,
             release: DEFAULT_RELEASE,
             hold: DEFAULT_HOLD,
+            release_debounce: None,
+            min_press: None,
+            max_clicks: None,
+            click_hold_policy: ClickHoldPolicy::HoldPreservesClicks,
+            auto_reset: false,
+            repeat: None,
+            repeat_accel: None,
+            ignore_initial_press: false,
+            hold_must_exceed: None,
+            click_ceiling: None,
+            max_tick_gap: None,
+            autofire_interval: None,
+            min_click_interval: None,
+            mode: Mode::default(),
+        }
+    }
+}
+
+impl From<SwitchType> for ButtonConfig<Duration> {
+    fn from(switch: SwitchType) -> Self {
+        let (debounce, release, hold) = switch.timings_ms();
+        Self::new(
+            Duration::from_millis(debounce),
+            Duration::from_millis(release),
+            Duration::from_millis(hold),
+            Mode::default(),
+        )
+    }
+}
+
+/// Timings in ticks of [TickCount](crate::instant::TickCount), assuming a tick per millisecond.
+impl Default for ButtonConfig<u32> {
+    fn default() -> Self {
+        Self {
+            debounce: DEFAULT_DEBOUNCE_MS,
+            release: DEFAULT_RELEASE_MS,
+            hold: DEFAULT_HOLD_MS,
+            release_debounce: None,
+            min_press: None,
+            max_clicks: None,
+            click_hold_policy: ClickHoldPolicy::HoldPreservesClicks,
+            auto_reset: false,
+            repeat: None,
+            repeat_accel: None,
+            ignore_initial_press: false,
+            hold_must_exceed: None,
+            click_ceiling: None,
+            max_tick_gap: None,
+            autofire_interval: None,
+            min_click_interval: None,
             mode: Mode::default(),
         }
     }
@@ -54,13 +255,62 @@ impl Default for ButtonConfig<embassy_time::Duration> {
             debounce: Duration::from_micros(DEFAULT_DEBOUNCE.as_micros() as u64),
             release: Duration::from_millis(DEFAULT_RELEASE.as_millis() as u64),
             hold: Duration::from_millis(DEFAULT_HOLD.as_millis() as u64),
+            release_debounce: None,
+            min_press: None,
+            max_clicks: None,
+            click_hold_policy: ClickHoldPolicy::HoldPreservesClicks,
+            auto_reset: false,
+            repeat: None,
+            repeat_accel: None,
+            ignore_initial_press: false,
+            hold_must_exceed: None,
+            click_ceiling: None,
+            max_tick_gap: None,
+            autofire_interval: None,
+            min_click_interval: None,
             mode: Mode::default(),
         }
     }
 }
 
+#[cfg(feature = "embassy")]
+impl From<SwitchType> for ButtonConfig<embassy_time::Duration> {
+    fn from(switch: SwitchType) -> Self {
+        let (debounce, release, hold) = switch.timings_ms();
+        Self::const_millis(debounce, release, hold, Mode::default())
+    }
+}
+
+#[cfg(feature = "embassy")]
+impl ButtonConfig<embassy_time::Duration> {
+    /// Returns new [ButtonConfig] from timings in milliseconds, usable in a `const` or `static` context.
+    pub const fn const_millis(debounce: u64, release: u64, hold: u64, mode: Mode) -> Self {
+        use embassy_time::Duration;
+        Self {
+            debounce: Duration::from_millis(debounce),
+            release: Duration::from_millis(release),
+            hold: Duration::from_millis(hold),
+            release_debounce: None,
+            min_press: None,
+            max_clicks: None,
+            click_hold_policy: ClickHoldPolicy::HoldPreservesClicks,
+            auto_reset: false,
+            repeat: None,
+            repeat_accel: None,
+            ignore_initial_press: false,
+            hold_must_exceed: None,
+            click_ceiling: None,
+            max_tick_gap: None,
+            autofire_interval: None,
+            min_click_interval: None,
+            mode,
+        }
+    }
+}
+
 /// Button direction.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Mode {
     /// Active 0.
     #[default]
@@ -79,4 +329,92 @@ impl Mode {
     pub const fn is_pulldown(&self) -> bool {
         !self.is_pullup()
     }
+
+    /// Returns the name of the mode, e.g. `"PullUp"`, to log it without `core::fmt`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Mode::PullUp => "PullUp",
+            Mode::PullDown => "PullDown",
+        }
+    }
+}
+
+/// What happens to the clicks counted before a hold in the same streak.
+///
+/// Truth table for a "double click, then hold" gesture:
+///
+/// | Policy                | `clicks()` | `holds()` |
+/// |-----------------------|------------|-----------|
+/// | `HoldPreservesClicks` | 2          | 1         |
+/// | `HoldCancelsClicks`   | 0          | 1         |
+///
+/// In both cases the press that turned into the hold is not counted as a click.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClickHoldPolicy {
+    /// Clicks before a hold are reported together with it.
+    #[default]
+    HoldPreservesClicks,
+    /// A hold discards the clicks counted before it.
+    HoldCancelsClicks,
+}
+
+/// A kind of physical switch, see [ButtonConfig::for_switch].
+///
+/// The timings are starting points, measure your hardware to tune them:
+///
+/// | Switch       | debounce | release | hold  |
+/// |--------------|----------|---------|-------|
+/// | `Tactile`    | 5ms      | 150ms   | 500ms |
+/// | `Membrane`   | 10ms     | 200ms   | 600ms |
+/// | `Reed`       | 2ms      | 150ms   | 500ms |
+/// | `Capacitive` | 2ms      | 250ms   | 700ms |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SwitchType {
+    /// A tactile push button. Most bounces measured in J. Ganssle's "A Guide to Debouncing"
+    /// settle within a few milliseconds.
+    Tactile,
+    /// A membrane or rubber dome key, bouncing longer and softer than a tactile one,
+    /// keyboard firmware commonly debounces them for 5 to 10 milliseconds.
+    Membrane,
+    /// A reed switch, typically bouncing for less than a millisecond according to manufacturer datasheets.
+    Reed,
+    /// A capacitive touch pad. There is no contact bounce, the short debounce rejects noise,
+    /// the longer timeouts account for slower finger movements.
+    Capacitive,
+}
+
+impl SwitchType {
+    /// Returns (debounce, release, hold) in milliseconds.
+    const fn timings_ms(self) -> (u64, u64, u64) {
+        match self {
+            Self::Tactile => (5, 150, 500),
+            Self::Membrane => (10, 200, 600),
+            Self::Reed => (2, 150, 500),
+            Self::Capacitive => (2, 250, 700),
+        }
+    }
+}
+
+/// An inconsistent [ButtonConfig], see [ButtonConfig::validate].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigError {
+    /// `debounce` is greater than `release`.
+    DebounceExceedsRelease,
+    /// `release` is greater than `hold`.
+    ReleaseExceedsHold,
+    /// `release` or `hold` is zero.
+    ZeroDuration,
+}
+
+impl core::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::DebounceExceedsRelease => "debounce time is greater than release time",
+            Self::ReleaseExceedsHold => "release time is greater than hold time",
+            Self::ZeroDuration => "release or hold time is zero",
+        })
+    }
 }
+
+impl core::error::Error for ConfigError {}
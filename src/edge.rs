@@ -0,0 +1,71 @@
+/// Pin edges recorded by an interrupt handler for [Button::process](crate::Button::process).
+///
+/// The latch is owned by the interrupt handler instead of the [Button](crate::Button),
+/// so the interrupt never touches the button: only this small type has to be shared,
+/// e.g. in a `critical_section::Mutex<RefCell<_>>`, while the main loop owns the button.
+///
+/// [on_edge](Self::on_edge) is the ISR-safe part: it only stores the level and the instant,
+/// without comparisons or event emission. Everything else runs in [Button::process](crate::Button::process).
+///
+/// ```
+/// use core::cell::Cell;
+/// use button_driver::{Button, ButtonConfig, EdgeLatch, FnPin, TickClock, TickCount};
+///
+/// let ticks = Cell::new(0);
+/// // The pin is not read, the levels come from the latch
+/// let mut button = Button::with_clock(FnPin(|| true), ButtonConfig::<u32>::default(), TickClock(&ticks));
+/// let mut latch = EdgeLatch::new();
+///
+/// // In the interrupt, a pull up pin going low
+/// latch.on_edge(false, TickCount(ticks.get()));
+///
+/// // In the main loop, take the edge under the lock and process it outside
+/// button.process(latch.take());
+/// assert!(button.raw_state().is_down());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EdgeLatch<I> {
+    /// The pin level reported by the last edge.
+    level: Option<bool>,
+    /// The instant of an edge not taken yet.
+    edge: Option<I>,
+}
+
+impl<I> EdgeLatch<I> {
+    /// Creates a latch without any edges.
+    pub const fn new() -> Self {
+        Self {
+            level: None,
+            edge: None,
+        }
+    }
+
+    /// Records a pin edge, e.g. from a GPIO interrupt.
+    ///
+    /// Only the last edge is kept, so process edges at least as often as the shortest press you want to detect.
+    pub fn on_edge(&mut self, level: bool, now: I) {
+        self.level = Some(level);
+        self.edge = Some(now);
+    }
+
+    /// Returns the pin level reported by the last edge, [None] before the first one.
+    pub const fn level(&self) -> Option<bool> {
+        self.level
+    }
+
+    /// Returns the instant of the edge not taken yet.
+    pub(crate) fn edge(self) -> Option<I> {
+        self.edge
+    }
+
+    /// Takes the edge not processed yet, keeping the level for the next call.
+    ///
+    /// Call it under the same lock as [on_edge](Self::on_edge) and hand the result
+    /// to [Button::process](crate::Button::process) after releasing the lock.
+    pub fn take(&mut self) -> Self {
+        Self {
+            level: self.level,
+            edge: self.edge.take(),
+        }
+    }
+}
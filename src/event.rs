@@ -0,0 +1,102 @@
+use core::ops::Sub;
+
+use crate::{Button, ClockSource, PinWrapper, StateKind, StateMachine};
+
+/// An event produced by a single [Button::tick](crate::Button::tick).
+///
+/// New events may be added in a minor release, so matches need a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ButtonEvent<D> {
+    /// Debounced press, the button went down.
+    Press,
+    /// The button went up after a press, more clicks may follow until the release timeout.
+    Release,
+    /// The button has been pressed long enough to be held.
+    HoldStart,
+    /// The button went up after being held for the given time.
+    HoldEnd(D),
+    /// The release timeout has passed, the gesture is finished.
+    Settled {
+        /// Amount of clicks in the gesture, see [Button::clicks](crate::Button::clicks).
+        clicks: usize,
+        /// Amount of holds in the gesture, see [Button::holds](crate::Button::holds).
+        holds: usize,
+        /// Duration of the last hold, see [Button::held_time](crate::Button::held_time).
+        held: Option<D>,
+    },
+}
+
+/// A finished gesture to compare the button against, see [Button::matches](crate::Button::matches).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gesture {
+    /// Exactly `n` clicks without holds.
+    Click(usize),
+    /// A single hold without clicks.
+    Hold,
+    /// A single hold preceded by exactly `n` clicks, e.g. `ClicksThenHold(2)` is "click, click, hold".
+    ClicksThenHold(usize),
+}
+
+/// The current finished gesture at once, see [Button::report](crate::Button::report).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GestureReport<D> {
+    /// See [Button::clicks](crate::Button::clicks).
+    pub clicks: usize,
+    /// See [Button::holds](crate::Button::holds).
+    pub holds: usize,
+    /// See [Button::held_time](crate::Button::held_time).
+    pub held_time: Option<D>,
+    /// See [Button::current_holding_time](crate::Button::current_holding_time).
+    pub current_hold: Option<D>,
+}
+
+/// Raw internals of the button for troubleshooting misfires, see [Button::diagnostics](crate::Button::diagnostics).
+///
+/// Unlike [GestureReport], the counters are not gated by the release timeout, attach it to bug reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Diagnostics<D> {
+    /// See [Button::clicks](crate::Button::clicks).
+    pub clicks: usize,
+    /// See [Button::holds](crate::Button::holds).
+    pub holds: usize,
+    /// See [Button::raw_clicks](crate::Button::raw_clicks).
+    pub raw_clicks: usize,
+    /// See [Button::raw_holds](crate::Button::raw_holds).
+    pub raw_holds: usize,
+    /// See [Button::state_kind](crate::Button::state_kind).
+    pub state: StateKind,
+    /// See [Button::elapsed_in_state](crate::Button::elapsed_in_state).
+    pub time_in_state: Option<D>,
+}
+
+/// An endless iterator ticking the button once per item, see [Button::events].
+///
+/// Each item is the event produced by the tick, or [None] if the button is idle.
+#[derive(Debug)]
+pub struct Events<'a, P, I, D, C, const K: usize, S> {
+    button: &'a mut Button<P, I, D, C, K, S>,
+}
+
+impl<'a, P, I, D, C, const K: usize, S> Events<'a, P, I, D, C, K, S> {
+    pub(crate) fn new(button: &'a mut Button<P, I, D, C, K, S>) -> Self {
+        Self { button }
+    }
+}
+
+impl<P, I, D, C, const K: usize, S> Iterator for Events<'_, P, I, D, C, K, S>
+where
+    P: PinWrapper,
+    I: Sub<I, Output = D> + Clone + PartialOrd,
+    D: Clone + PartialOrd + Default,
+    C: ClockSource<I, D>,
+    S: StateMachine,
+{
+    type Item = Option<ButtonEvent<D>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.button.update())
+    }
+}
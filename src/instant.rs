@@ -1,9 +1,15 @@
-use core::{ops::Sub, time::Duration};
+use core::{cell::Cell, ops::Sub, time::Duration};
+
+#[cfg(target_has_atomic = "32")]
+use core::sync::atomic::{AtomicU32, Ordering};
 
 /// An abstraction for retrieving the current time.
 ///
 /// The underlying counter shell be monotonic in order for the crate to
 /// operate correctly.
+///
+/// For RTIC, wrap the instant of an `rtic_monotonics` monotonic into a local type and call `Mono::now()`,
+/// see the `stm32-rtic` example.
 pub trait InstantProvider<D = Duration>
 where
     // `Clone` is less strict then `Copy` and usually implemented using it.
@@ -14,7 +20,86 @@ where
 
     /// Returns the amount of time elapsed since this instant.
     fn elapsed(&self) -> D {
-        Self::now() - self.clone()
+        self.elapsed_since(&Self::now())
+    }
+
+    /// Returns the amount of time elapsed from this instant to `now`,
+    /// so a single reading of a costly clock can be reused.
+    fn elapsed_since(&self, now: &Self) -> D {
+        now.clone() - self.clone()
+    }
+
+    /// Returns the amount of time elapsed since this instant,
+    /// or [None] if the clock went backwards and the subtraction could underflow.
+    fn checked_elapsed(&self) -> Option<D>
+    where
+        Self: PartialOrd,
+    {
+        self.checked_elapsed_since(&Self::now())
+    }
+
+    /// Returns the amount of time elapsed from this instant to `now`, see [elapsed_since](Self::elapsed_since),
+    /// or [None] if `now` is before this instant.
+    ///
+    /// This is what the [Button](crate::Button) measures time with through [GlobalClock].
+    fn checked_elapsed_since(&self, now: &Self) -> Option<D>
+    where
+        Self: PartialOrd,
+    {
+        (*now >= *self).then(|| self.elapsed_since(now))
+    }
+}
+
+/// A source of the current time stored inside a [Button](crate::Button).
+///
+/// Unlike [InstantProvider], which is a single global clock per instant type,
+/// different buttons can read different clocks of the same instant type.
+pub trait ClockSource<I, D = Duration>
+where
+    I: Sub<I, Output = D> + Clone,
+{
+    /// Returns an instant corresponding to "now".
+    fn now(&self) -> I;
+
+    /// Returns the amount of time elapsed since the given instant, see [checked_elapsed_since](Self::checked_elapsed_since),
+    /// saturating at zero if the clock went backwards.
+    fn elapsed(&self, instant: &I) -> D
+    where
+        I: PartialOrd,
+        D: Default,
+    {
+        self.checked_elapsed_since(instant, &self.now())
+            .unwrap_or_default()
+    }
+
+    /// Returns the amount of time elapsed from `instant` to `now`, or [None] if `now` is before `instant`.
+    ///
+    /// The [Button](crate::Button) measures all its timeouts with it and treats [None] as zero elapsed time.
+    fn checked_elapsed_since(&self, instant: &I, now: &I) -> Option<D>
+    where
+        I: PartialOrd,
+    {
+        (now >= instant).then(|| now.clone() - instant.clone())
+    }
+}
+
+/// The default zero-sized [ClockSource], reading the global [InstantProvider] of the instant type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GlobalClock;
+
+impl<I, D> ClockSource<I, D> for GlobalClock
+where
+    I: InstantProvider<D>,
+{
+    fn now(&self) -> I {
+        I::now()
+    }
+
+    fn checked_elapsed_since(&self, instant: &I, now: &I) -> Option<D>
+    where
+        I: PartialOrd,
+    {
+        instant.checked_elapsed_since(now)
     }
 }
 
@@ -25,9 +110,204 @@ impl InstantProvider<std::time::Duration> for std::time::Instant {
     }
 }
 
+/// A wall-clock [std::time::SystemTime] based instant, for hosts without a monotonic clock.
+///
+/// Wall-clock time may jump backwards, e.g. on NTP sync,
+/// such jumps are treated as zero elapsed time instead of underflowing.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SystemInstant(pub std::time::SystemTime);
+
+#[cfg(feature = "std")]
+impl Sub for SystemInstant {
+    type Output = std::time::Duration;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.0.duration_since(rhs.0).unwrap_or_default()
+    }
+}
+
+#[cfg(feature = "std")]
+impl InstantProvider<std::time::Duration> for SystemInstant {
+    fn now() -> Self {
+        Self(std::time::SystemTime::now())
+    }
+}
+
+/// The number of ticks counted so far by [TickCount::increment].
+#[cfg(target_has_atomic = "32")]
+static TICKS: AtomicU32 = AtomicU32::new(0);
+
+/// A loop counter instant for systems without any timer, durations are measured in ticks, see `ButtonConfig<u32>`.
+///
+/// The counter wraps after [u32::MAX] ticks, elapsed time is measured with wrapping arithmetic,
+/// so timeouts keep working across the wrap as long as they are shorter than [u32::MAX] ticks.
+///
+/// On targets with 32-bit atomics it has a global counter: call [TickCount::increment] once per loop iteration,
+/// e.g. right before [Button::tick](crate::Button::tick). Elsewhere, e.g. on AVR or MSP430,
+/// count the ticks yourself and read them with a [TickClock].
+/// A custom [ClockSource] of [TickCount] should forward [ClockSource::checked_elapsed_since]
+/// to [InstantProvider::checked_elapsed_since] to keep the wrapping arithmetic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TickCount(pub u32);
+
+#[cfg(target_has_atomic = "32")]
+impl TickCount {
+    /// Advances the global counter by one tick.
+    ///
+    /// Not atomic, so that it works on targets without atomic read-modify-write;
+    /// increment from a single place only.
+    pub fn increment() {
+        TICKS.store(
+            TICKS.load(Ordering::Relaxed).wrapping_add(1),
+            Ordering::Relaxed,
+        );
+    }
+}
+
+impl Sub for TickCount {
+    type Output = u32;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.0.wrapping_sub(rhs.0)
+    }
+}
+
+#[cfg(target_has_atomic = "32")]
+impl InstantProvider<u32> for TickCount {
+    fn now() -> Self {
+        Self(TICKS.load(Ordering::Relaxed))
+    }
+
+    /// Never [None], an instant "after" `now` is one from before the wrap.
+    fn checked_elapsed_since(&self, now: &Self) -> Option<u32> {
+        Some(self.elapsed_since(now))
+    }
+}
+
+/// A [ClockSource] reading a [TickCount] from a counter owned by the caller,
+/// for targets without the global counter or several independently counted loops.
+///
+/// ```
+/// use core::cell::Cell;
+/// use button_driver::{Button, ButtonConfig, FnPin, TickClock};
+///
+/// let ticks = Cell::new(0);
+/// // A released pull up pin
+/// let pin = FnPin(|| true);
+/// let mut button = Button::with_clock(pin, ButtonConfig::<u32>::default(), TickClock(&ticks));
+/// for _ in 0..10 {
+///     ticks.set(ticks.get().wrapping_add(1));
+///     button.tick();
+/// }
+/// assert!(button.raw_state().is_released());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TickClock<'a>(pub &'a Cell<u32>);
+
+impl ClockSource<TickCount, u32> for TickClock<'_> {
+    fn now(&self) -> TickCount {
+        TickCount(self.0.get())
+    }
+
+    /// Never [None], an instant "after" `now` is one from before the wrap.
+    fn checked_elapsed_since(&self, instant: &TickCount, now: &TickCount) -> Option<u32> {
+        Some(*now - *instant)
+    }
+}
+
 #[cfg(feature = "embassy")]
 impl InstantProvider<embassy_time::Duration> for embassy_time::Instant {
     fn now() -> Self {
         embassy_time::Instant::now()
     }
 }
+
+/// On native targets `web_time::Instant` is [std::time::Instant], already covered by the `std` feature.
+#[cfg(all(
+    feature = "web_time",
+    any(target_family = "wasm", not(feature = "std"))
+))]
+impl InstantProvider<web_time::Duration> for web_time::Instant {
+    fn now() -> Self {
+        web_time::Instant::now()
+    }
+}
+
+/// `time::Instant` wraps [std::time::Instant], measuring signed `time::Duration`s.
+///
+/// If the clock goes backwards, the negative difference is treated as zero elapsed time by the [Button](crate::Button).
+///
+/// ```
+/// use button_driver::{Button, ButtonConfig, FnPin, InstantProvider, Mode};
+///
+/// let config = ButtonConfig::new(
+///     time::Duration::milliseconds(1),
+///     time::Duration::milliseconds(150),
+///     time::Duration::milliseconds(500),
+///     Mode::PullDown,
+/// );
+/// let mut button = Button::<_, time::Instant, time::Duration>::new(FnPin(|| false), config);
+/// button.tick();
+/// assert!(button.raw_state().is_released());
+///
+/// let start = <time::Instant as InstantProvider<time::Duration>>::now();
+/// assert!(start.elapsed() >= time::Duration::ZERO);
+/// ```
+#[cfg(feature = "time")]
+#[allow(deprecated)]
+impl InstantProvider<time::Duration> for time::Instant {
+    fn now() -> Self {
+        time::Instant::now()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+pub(crate) mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use super::ClockSource;
+    use crate::{pin_wrapper::tests::MockPin, Button};
+
+    /// A manually advanced clock, counting microseconds.
+    #[derive(Debug, Default, Clone)]
+    pub struct MockClock(Arc<AtomicU64>);
+
+    impl ClockSource<Duration> for MockClock {
+        fn now(&self) -> Duration {
+            Duration::from_micros(self.0.load(Ordering::SeqCst))
+        }
+    }
+
+    impl MockClock {
+        /// Move the clock forward.
+        pub fn advance(&self, duration: Duration) {
+            self.0
+                .fetch_add(duration.as_micros() as u64, Ordering::SeqCst);
+        }
+    }
+
+    impl<const K: usize> Button<MockPin, Duration, Duration, MockClock, K> {
+        /// Advance the clock and tick.
+        pub fn advance(&mut self, duration: Duration) {
+            self.clock.advance(duration);
+            self.tick();
+        }
+
+        /// Press the pin for the given time and release it, ticking on every change.
+        pub fn click_for(&mut self, duration: Duration) {
+            self.pin.press_raw();
+            self.tick();
+            self.advance(duration);
+            self.tick();
+            self.pin.release_raw();
+            self.tick();
+        }
+    }
+}
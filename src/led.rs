@@ -0,0 +1,68 @@
+use core::ops::{Deref, DerefMut, Sub};
+
+use embedded_hal::digital::{OutputPin, PinState};
+
+use crate::{Button, ClockSource, PinWrapper, StateMachine};
+
+/// A [Button] mirroring its debounced pressed state onto an LED, see [Button::with_led].
+///
+/// Dereferences to the inner [Button], so all the accessors are available as usual.
+#[derive(Clone, Debug)]
+pub struct LedButton<B, L> {
+    /// An inner button.
+    pub button: B,
+    /// An LED showing the debounced pressed state.
+    pub led: L,
+    /// The LED state while the button is pressed.
+    active: PinState,
+}
+
+impl<P, I, D, C, L, const K: usize, S> LedButton<Button<P, I, D, C, K, S>, L>
+where
+    P: PinWrapper,
+    I: Sub<I, Output = D> + Clone + PartialOrd,
+    D: Clone + PartialOrd + Default,
+    C: ClockSource<I, D>,
+    S: StateMachine,
+    L: OutputPin,
+{
+    /// Creates a new [LedButton], the LED is set to `active` while the button is pressed.
+    pub fn new(button: Button<P, I, D, C, K, S>, led: L, active: PinState) -> Self {
+        Self {
+            button,
+            led,
+            active,
+        }
+    }
+
+    /// Updates button state, see [Button::tick], and mirrors it onto the LED.
+    /// LED errors are ignored.
+    pub fn tick(&mut self) {
+        self.button.tick();
+        let state = if self.button.read_debounced() {
+            self.active
+        } else {
+            !self.active
+        };
+        let _ = self.led.set_state(state);
+    }
+
+    /// Returns the inner button and LED.
+    pub fn into_inner(self) -> (Button<P, I, D, C, K, S>, L) {
+        (self.button, self.led)
+    }
+}
+
+impl<B, L> Deref for LedButton<B, L> {
+    type Target = B;
+
+    fn deref(&self) -> &B {
+        &self.button
+    }
+}
+
+impl<B, L> DerefMut for LedButton<B, L> {
+    fn deref_mut(&mut self) -> &mut B {
+        &mut self.button
+    }
+}
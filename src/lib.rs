@@ -2,18 +2,56 @@
 #![warn(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use core::time::Duration;
+use core::{marker::PhantomData, ops::Sub, time::Duration};
 
-pub use config::{ButtonConfig, Mode};
-pub use instant::InstantProvider;
-pub use pin_wrapper::PinWrapper;
+pub use bank::BankButtons;
+pub use config::{ButtonConfig, ClickHoldPolicy, ConfigError, Mode, SwitchType};
+pub use edge::EdgeLatch;
+pub use event::{ButtonEvent, Diagnostics, Events, Gesture, GestureReport};
+pub use instant::{ClockSource, GlobalClock, InstantProvider, TickClock, TickCount};
+pub use machine::{StandardMachine, StateMachine};
 
+#[cfg(feature = "std")]
+pub use instant::SystemInstant;
+#[cfg(feature = "embedded_hal")]
+pub use led::LedButton;
+pub use pin_wrapper::{Debounced, FnPin, PinRef, PinWrapper, PortPin, PortWrapper, ThresholdPin};
+#[cfg(feature = "heapless")]
+pub use queue::QueuedButton;
+pub use snapshot::Snapshot;
+
+#[cfg(feature = "gpio_cdev")]
+pub use pin_wrapper::CdevPin;
+#[cfg(feature = "embedded_hal")]
+pub use pin_wrapper::{FalliblePin, OutputPinReadback};
+
+/// Buttons sharing one port.
+mod bank;
 /// Button configuration.
 pub mod config;
+/// Pin edges recorded by interrupts.
+mod edge;
+/// High-level button events.
+mod event;
 /// Different current global time sources.
 pub mod instant;
+/// LED feedback of the button state.
+#[cfg(feature = "embedded_hal")]
+mod led;
+/// Pluggable transition logic.
+mod machine;
 /// Wrappers for different APIs.
 mod pin_wrapper;
+/// Common imports.
+pub mod prelude;
+/// Handing events over through a lock-free queue.
+#[cfg(feature = "heapless")]
+mod queue;
+/// Persisting the button across restarts.
+mod snapshot;
+/// Deterministic simulation of button gestures.
+#[cfg(any(feature = "testing", all(test, feature = "std")))]
+pub mod testing;
 
 #[cfg(all(test, feature = "std"))]
 mod tests;
@@ -22,30 +60,98 @@ mod tests;
 ///
 /// The crate is designed to provide a finished ([`released`](ButtonConfig#structfield.release)) state by the accessor methods.
 /// However, it is also possible to get the `raw` state using the corresponding methods.
-#[derive(Clone, Debug)]
-pub struct Button<P, I, D = Duration> {
+///
+/// The current time is read from a [ClockSource], by default the global [InstantProvider] of `I`.
+///
+/// `K` is the capacity of the event history, see `Button::history` under the `heapless` feature.
+///
+/// `S` is the [StateMachine] implementing the click and hold algorithm.
+///
+/// The button owns no pointers or cells, so it is [Send] and [Sync] whenever `P`, `I`, `D` and `C` are,
+/// e.g. to share it between threads behind a mutex.
+///
+/// [Debug] skips the pin and the clock, so it does not require them to be [Debug].
+/// It starts with the [name](Button::with_name) of the button, if set.
+#[derive(Clone)]
+pub struct Button<P, I, D = Duration, C = GlobalClock, const K: usize = 0, S = StandardMachine> {
     /// An inner pin.
     pub pin: P,
+    /// A label for diagnostics, see [with_name](Button::with_name).
+    name: &'static str,
     state: State<I>,
     clicks: usize,
+    /// Whether the current press was counted as a click, see [max_clicks](ButtonConfig::max_clicks).
+    click_counted: bool,
     held: Option<D>,
     holds: usize,
+    clicks_before_hold: usize,
+    /// The instant of the last press edge, see [inter_click_time](Button::inter_click_time).
+    last_press: Option<I>,
+    inter_click: Option<D>,
+    /// The release instant of the last click, see [min_click_interval](ButtonConfig::min_click_interval).
+    last_click: Option<I>,
+    /// Whether a hold has ended and not been taken by [hold_completed](Button::hold_completed) yet.
+    hold_ended: bool,
+    /// Whether a live hold has started and not been taken by [long_press_triggered](Button::long_press_triggered) yet.
+    hold_started: bool,
+    /// Whether the last [Held](State::Held) press has not exceeded [hold_must_exceed](ButtonConfig::hold_must_exceed) yet,
+    /// so it is not counted as a hold and a release makes it a click.
+    hold_pending: bool,
+    /// Auto-repeats in the current hold, see [repeat](ButtonConfig::repeat).
+    repeats: usize,
+    /// The instant of the hold start or the last auto-repeat.
+    last_repeat: Option<I>,
+    /// The instant of the last [fire](Button::fire).
+    last_fire: Option<I>,
+    paused: bool,
+    /// Toggled by every click, see [latched](Button::latched).
+    latched: bool,
+    total_presses: u32,
+    /// The instant the pin was first seen released while [Pressed](State::Pressed).
+    release_bounce: Option<I>,
+    /// The event produced by the last tick.
+    event: Option<ButtonEvent<D>>,
+    /// The state change of the last tick, see [last_change](Button::last_change).
+    last_change: Option<(StateKind, StateKind)>,
+    /// The last event not taken by [next_event](Button::next_event) yet.
+    pending: Option<ButtonEvent<D>>,
+    /// The instant of the last tick, see [max_tick_gap](ButtonConfig::max_tick_gap).
+    last_tick: Option<I>,
+    /// The instant the button was started or first ticked, see [uptime](Button::uptime).
+    started: Option<I>,
+    /// The mode before the last [config_mut](Button::config_mut), checked on the next tick.
+    prev_mode: Option<Mode>,
+    /// Called with (old, new) state on every state change, see [on_transition](Button::on_transition).
+    transition_hook: Option<TransitionHook<I>>,
+    #[cfg(feature = "heapless")]
+    history: heapless::Vec<ButtonEvent<D>, K>,
     config: ButtonConfig<D>,
+    clock: C,
+    machine: PhantomData<fn() -> S>,
 }
 
+/// A function called with (old, new) state on every state change, see [Button::on_transition].
+pub type TransitionHook<I> = fn(&State<I>, &State<I>);
+
 /// Represents current button state.
 ///
 ///
 /// State machine diagram:
 ///```ignore
-/// Down => Pressed | Released
-/// Pressed => Held => Up
+/// Down => Pressed | Up | Released
+/// Pressed => Held | Up
+/// Held => Up
 /// Up => Released | Down
-/// Held => Released
 /// Released => Down
 /// Unknown => Down | Released
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// A hold is released through [Up](State::Up) like a click, so taps within the
+/// [release](ButtonConfig::release) timeout after a hold are counted in the same streak.
+///
+/// New states may be added in a minor release, so matches need a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum State<I> {
     /// The button has been just pressed, so it is in *down* position.
     Down(I),
@@ -61,7 +167,65 @@ pub enum State<I> {
     Unknown,
 }
 
+/// A [State] variant without the instant, e.g. to key a map or a match table on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum StateKind {
+    /// See [State::Down].
+    Down,
+    /// See [State::Pressed].
+    Pressed,
+    /// See [State::Up].
+    Up,
+    /// See [State::Held].
+    Held,
+    /// See [State::Released].
+    Released,
+    /// See [State::Unknown].
+    Unknown,
+}
+
+impl<I> State<I> {
+    /// Returns the variant of the state without the instant.
+    pub const fn kind(&self) -> StateKind {
+        match self {
+            Self::Down(_) => StateKind::Down,
+            Self::Pressed(_) => StateKind::Pressed,
+            Self::Up(_) => StateKind::Up,
+            Self::Held(_) => StateKind::Held,
+            Self::Released => StateKind::Released,
+            Self::Unknown => StateKind::Unknown,
+        }
+    }
+
+    /// Returns the name of the state variant, e.g. `"Pressed"`, to log it without `core::fmt`,
+    /// e.g. with `ufmt` or over a serial port.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Down(_) => "Down",
+            Self::Pressed(_) => "Pressed",
+            Self::Up(_) => "Up",
+            Self::Held(_) => "Held",
+            Self::Released => "Released",
+            Self::Unknown => "Unknown",
+        }
+    }
+}
+
 impl<I: PartialEq> State<I> {
+    /// Returns the instant stored in the state, [None] for [Released](State::Released) and [Unknown](State::Unknown).
+    pub const fn instant(&self) -> Option<&I> {
+        match self {
+            Self::Down(instant)
+            | Self::Pressed(instant)
+            | Self::Up(instant)
+            | Self::Held(instant) => Some(instant),
+            Self::Released | Self::Unknown => None,
+        }
+    }
+
     /// Returns [true] if the state is [Down](State::Down).
     pub fn is_down(&self) -> bool {
         matches!(self, Self::Down(_))
@@ -96,26 +260,192 @@ impl<I: PartialEq> State<I> {
 impl<P, I, D> Button<P, I, D>
 where
     P: PinWrapper,
-    I: InstantProvider<D> + PartialEq,
-    D: Clone + Ord,
+    I: InstantProvider<D> + PartialOrd,
+    D: Clone + PartialOrd + Default,
 {
     /// Creates a new [Button].
     pub const fn new(pin: P, config: ButtonConfig<D>) -> Self {
+        Self::with_clock(pin, config, GlobalClock)
+    }
+
+    /// Creates a new [Button] and starts its [uptime](Self::uptime) now.
+    ///
+    /// [new](Self::new) is `const` and can not read the time, so its uptime starts at the first tick instead.
+    pub fn start(pin: P, config: ButtonConfig<D>) -> Self {
+        let mut button = Self::new(pin, config);
+        button.started = Some(I::now());
+        button
+    }
+
+    /// Creates a new [Button] with a copy of a config shared by several buttons,
+    /// handy when `D` is not [Copy].
+    pub fn from_config_ref(pin: P, config: &ButtonConfig<D>) -> Self {
+        Self::new(pin, config.clone())
+    }
+
+    /// Restores a button saved by [snapshot](Self::snapshot).
+    ///
+    /// A state with an instant is restored as [Unknown](State::Unknown), since the instant is stale after a restart.
+    pub fn from_snapshot(pin: P, snapshot: Snapshot<D>) -> Self {
+        let mut button = Self::new(pin, snapshot.config);
+        button.clicks = snapshot.clicks;
+        button.holds = snapshot.holds;
+        if snapshot.state == StateKind::Released {
+            button.state = State::Released;
+        }
+        button
+    }
+}
+
+impl<P, I, D, C> Button<P, I, D, C>
+where
+    P: PinWrapper,
+    I: Sub<I, Output = D> + Clone + PartialOrd,
+    D: Clone + PartialOrd + Default,
+    C: ClockSource<I, D>,
+{
+    /// Creates a new [Button] reading the time from the given [ClockSource].
+    pub const fn with_clock(pin: P, config: ButtonConfig<D>, clock: C) -> Self {
         Self {
             pin,
+            name: "",
             config,
+            clock,
             state: State::Unknown,
             clicks: 0,
+            click_counted: false,
             holds: 0,
             held: None,
+            clicks_before_hold: 0,
+            last_press: None,
+            inter_click: None,
+            last_click: None,
+            hold_ended: false,
+            hold_started: false,
+            hold_pending: false,
+            repeats: 0,
+            last_repeat: None,
+            last_fire: None,
+            paused: false,
+            latched: false,
+            total_presses: 0,
+            release_bounce: None,
+            event: None,
+            last_change: None,
+            pending: None,
+            last_tick: None,
+            started: None,
+            prev_mode: None,
+            transition_hook: None,
+            #[cfg(feature = "heapless")]
+            history: heapless::Vec::new(),
+            machine: PhantomData,
         }
     }
+}
+
+impl<P, I, D, C, const K: usize, S> Button<P, I, D, C, K, S>
+where
+    P: PinWrapper,
+    I: Sub<I, Output = D> + Clone + PartialOrd,
+    D: Clone + PartialOrd + Default,
+    C: ClockSource<I, D>,
+    S: StateMachine,
+{
+    /// Converts the button into one keeping the last `H` events, see [history](Self::history).
+    #[cfg(feature = "heapless")]
+    pub fn with_history<const H: usize>(self) -> Button<P, I, D, C, H, S> {
+        self.convert()
+    }
+
+    /// Converts the button into one driven by the given [StateMachine].
+    /// The event history, if any, is cleared.
+    pub fn with_machine<T: StateMachine>(self) -> Button<P, I, D, C, K, T> {
+        self.convert()
+    }
+
+    /// Sets a label shown in the [Debug] output, e.g. to tell the buttons of a keypad apart in logs.
+    pub fn with_name(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Returns the label set by [with_name](Self::with_name), empty by default.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Moves the button into another history capacity and state machine, the history is cleared.
+    fn convert<const H: usize, T>(self) -> Button<P, I, D, C, H, T> {
+        Button {
+            pin: self.pin,
+            name: self.name,
+            state: self.state,
+            clicks: self.clicks,
+            click_counted: self.click_counted,
+            held: self.held,
+            holds: self.holds,
+            clicks_before_hold: self.clicks_before_hold,
+            last_press: self.last_press,
+            inter_click: self.inter_click,
+            last_click: self.last_click,
+            hold_ended: self.hold_ended,
+            hold_started: self.hold_started,
+            hold_pending: self.hold_pending,
+            repeats: self.repeats,
+            last_repeat: self.last_repeat,
+            last_fire: self.last_fire,
+            paused: self.paused,
+            latched: self.latched,
+            total_presses: self.total_presses,
+            release_bounce: self.release_bounce,
+            event: self.event,
+            last_change: self.last_change,
+            pending: self.pending,
+            last_tick: self.last_tick,
+            started: self.started,
+            prev_mode: self.prev_mode,
+            transition_hook: self.transition_hook,
+            #[cfg(feature = "heapless")]
+            history: heapless::Vec::new(),
+            config: self.config,
+            clock: self.clock,
+            machine: PhantomData,
+        }
+    }
+
+    /// Couples the button with an LED showing the debounced pressed state on every tick,
+    /// the LED is set to `active` while the button is pressed.
+    #[cfg(feature = "embedded_hal")]
+    pub fn with_led<L>(self, led: L, active: embedded_hal::digital::PinState) -> LedButton<Self, L>
+    where
+        L: embedded_hal::digital::OutputPin,
+    {
+        LedButton::new(self, led, active)
+    }
+
+    /// Couples the button with the sending half of a [heapless::spsc] queue, every event is pushed into it on tick,
+    /// e.g. to tick in an interrupt and handle the events in a task.
+    #[cfg(feature = "heapless")]
+    pub fn with_producer<'a, const N: usize>(
+        self,
+        producer: heapless::spsc::Producer<'a, ButtonEvent<D>, N>,
+    ) -> QueuedButton<'a, Self, D, N> {
+        QueuedButton::new(self, producer)
+    }
+
+    /// Returns the last `K` events produced by [tick](Self::tick), the oldest first.
+    #[cfg(feature = "heapless")]
+    pub const fn history(&self) -> &heapless::Vec<ButtonEvent<D>, K> {
+        &self.history
+    }
 
     /// Returns the number of clicks that happened before the last release.
     /// Returns 0 if clicks are still being counted or a new streak has started.
+    /// Saturates at [usize::MAX] instead of wrapping, limited by [click_ceiling](ButtonConfig::click_ceiling).
     pub fn clicks(&self) -> usize {
         if self.state == State::Released {
-            self.clicks
+            self.reported_clicks()
         } else {
             0
         }
@@ -123,6 +453,10 @@ where
 
     /// Returns the number of holds (how many times the button was held) that happened before the last release.
     /// Returns 0 if clicks or holds are still being counted or a new streak has started.
+    ///
+    /// A hold is counted once the press lasts [hold](ButtonConfig::hold), i.e. on the [Pressed](State::Pressed)
+    /// to [Held](State::Held) transition, or once [hold_must_exceed](ButtonConfig::hold_must_exceed) is exceeded.
+    /// See [raw_holds](Self::raw_holds) for the live count.
     pub fn holds(&self) -> usize {
         if self.state == State::Released {
             self.holds
@@ -131,6 +465,34 @@ where
         }
     }
 
+    /// Returns the number of clicks that happened before the first hold of the last streak,
+    /// e.g. 2 for a "double click then hold" gesture, even if more clicks followed the hold.
+    /// Returns 0 if there was no hold or the streak is not released yet.
+    pub fn clicks_before_hold(&self) -> usize {
+        if self.state == State::Released {
+            self.clicks_before_hold
+        } else {
+            0
+        }
+    }
+
+    /// Returns the number of taps after a hold in the current streak, including a live one,
+    /// e.g. for "hold to arm, then tap to increment" gestures.
+    ///
+    /// With a single pin a tap can not happen while the button is held, so it is sensed as a click
+    /// following the hold within the [release](ButtonConfig::release) timeout, which keeps the streak going.
+    /// Taps are counted from the first hold of the streak, or from the last one with
+    /// [HoldCancelsClicks](ClickHoldPolicy::HoldCancelsClicks). Returns 0 if there was no hold.
+    pub fn taps_during_hold(&self) -> usize {
+        if self.holds == 0 {
+            0
+        } else if self.config.click_hold_policy == ClickHoldPolicy::HoldCancelsClicks {
+            self.clicks
+        } else {
+            self.clicks.saturating_sub(self.clicks_before_hold)
+        }
+    }
+
     /// Resets clicks amount and held time after release.
     ///
     /// Example:
@@ -152,11 +514,141 @@ where
     pub fn reset(&mut self) {
         if self.state == State::Released {
             self.clicks = 0;
+            self.click_counted = false;
+            self.holds = 0;
+            self.clicks_before_hold = 0;
+            self.held = None;
+        }
+    }
+
+    /// Resets only the clicks after release, keeping the holds, e.g. to consume clicks in one UI stage
+    /// and holds in another. Like [reset](Self::reset), does nothing until the release timeout.
+    pub fn reset_clicks(&mut self) {
+        if self.state == State::Released {
+            self.clicks = 0;
+            self.click_counted = false;
+        }
+    }
+
+    /// Resets only the holds, the held time and [clicks_before_hold](Self::clicks_before_hold) after release,
+    /// keeping the clicks. Like [reset](Self::reset), does nothing until the release timeout.
+    pub fn reset_holds(&mut self) {
+        if self.state == State::Released {
             self.holds = 0;
+            self.clicks_before_hold = 0;
             self.held = None;
         }
     }
 
+    /// Ends the current press as if the button was released and the release timeout has passed,
+    /// e.g. when the app navigates away mid-hold. A hold is finalized with the time held until now,
+    /// see [held_time](Self::held_time), while an undebounced press is discarded. No event is produced.
+    ///
+    /// Does nothing if the button is [Released](State::Released) or [Unknown](State::Unknown).
+    /// If the pin is still pressed, the next [tick](Self::tick) starts a new press, [pause](Self::pause) the button to avoid it.
+    pub fn force_release(&mut self) {
+        match &self.state {
+            State::Held(_) if self.hold_pending => {}
+            State::Held(since) => {
+                let since = since.clone();
+                self.end_hold(&self.clock.now(), &since);
+            }
+            State::Down(_) => self.uncount_click(),
+            State::Pressed(_) | State::Up(_) => {}
+            State::Released | State::Unknown => return,
+        }
+        self.release_bounce = None;
+        self.state = State::Released;
+    }
+
+    /// Returns the clicks of the finished gesture and [resets](Self::reset_clicks) them at once,
+    /// so they are handled exactly once. Returns [None] if there are no clicks to take.
+    pub fn consume_click(&mut self) -> Option<usize> {
+        let clicks = self.clicks();
+        self.reset_clicks();
+        (clicks > 0).then_some(clicks)
+    }
+
+    /// Returns the holds of the finished gesture and [resets](Self::reset_holds) them at once,
+    /// so they are handled exactly once. Returns [None] if there are no holds to take.
+    pub fn consume_hold(&mut self) -> Option<usize> {
+        let holds = self.holds();
+        self.reset_holds();
+        (holds > 0).then_some(holds)
+    }
+
+    /// Returns the number of debounced presses over the button lifetime.
+    ///
+    /// Unlike [clicks](Self::clicks), it is not cleared by [reset](Self::reset) or [resume](Self::resume),
+    /// only by [reset_stats](Self::reset_stats). Saturates at [u32::MAX] instead of wrapping.
+    pub const fn total_presses(&self) -> u32 {
+        self.total_presses
+    }
+
+    /// Clears the lifetime statistics, see [total_presses](Self::total_presses).
+    pub fn reset_stats(&mut self) {
+        self.total_presses = 0;
+    }
+
+    /// Pauses the button: [tick](Self::tick) does nothing and the state is frozen until [resume](Self::resume).
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resumes a paused button.
+    ///
+    /// The state is reset to [Unknown](State::Unknown), the counters, the one-shots and the events are cleared,
+    /// so neither an edge that happened while paused nor anything from before the pause is acted upon.
+    /// The [latched](Self::latched) state and the lifetime statistics are kept.
+    pub fn resume(&mut self) {
+        if self.paused {
+            self.paused = false;
+            self.restart();
+            self.event = None;
+            self.pending = None;
+            self.last_change = None;
+            self.last_tick = None;
+        }
+    }
+
+    /// Resets the state to [Unknown](State::Unknown) and clears the counters and the press timing.
+    fn restart(&mut self) {
+        self.state = State::Unknown;
+        self.clicks = 0;
+        self.click_counted = false;
+        self.holds = 0;
+        self.clicks_before_hold = 0;
+        self.held = None;
+        self.last_press = None;
+        self.inter_click = None;
+        self.last_click = None;
+        self.hold_ended = false;
+        self.hold_started = false;
+        self.hold_pending = false;
+        self.repeats = 0;
+        self.last_repeat = None;
+        self.last_fire = None;
+        self.release_bounce = None;
+    }
+
+    /// Returns the state of a software toggle switch, flipped by every click, e.g. for a power button.
+    ///
+    /// It flips as soon as a press is released, without waiting for the release timeout.
+    /// Holds and presses shorter than [min_press](ButtonConfig::min_press) do not flip it.
+    pub const fn latched(&self) -> bool {
+        self.latched
+    }
+
+    /// Sets the [latched](Self::latched) state, e.g. to restore it after a restart.
+    pub fn set_latched(&mut self, latched: bool) {
+        self.latched = latched;
+    }
+
+    /// Returns [true] if the button is paused.
+    pub const fn is_paused(&self) -> bool {
+        self.paused
+    }
+
     /// Returns [true] if the button was pressed once before release.
     pub fn is_clicked(&self) -> bool {
         self.clicks() == 1
@@ -172,6 +664,67 @@ where
         self.clicks() == 3
     }
 
+    /// Returns `(clicks, holds)` of the finished gesture, e.g. to match `Some((2, 2))` directly.
+    /// Returns [None] if the gesture is not finished yet or there was none.
+    pub fn clicks_and_holds(&self) -> Option<(usize, usize)> {
+        let gesture = (self.clicks(), self.holds());
+        (gesture != (0, 0)).then_some(gesture)
+    }
+
+    /// Returns [true] if the finished gesture is the given one.
+    ///
+    /// ```ignore
+    /// if button.matches(Gesture::ClicksThenHold(1)) {
+    ///     println!("Click + hold");
+    /// } else if button.matches(Gesture::Click(3)) {
+    ///     println!("Triple click");
+    /// }
+    /// ```
+    pub fn matches(&self, gesture: Gesture) -> bool {
+        match gesture {
+            Gesture::Click(n) => self.holds() == 0 && self.clicks() == n,
+            Gesture::Hold => self.holds() == 1 && self.clicks() == 0,
+            Gesture::ClicksThenHold(n) => self.holds() == 1 && self.clicks_before_hold() == n,
+        }
+    }
+
+    /// Returns the finished gesture and the live hold at once, e.g. to log the whole outcome.
+    ///
+    /// Like the accessors it is made of, it does not [reset](Self::reset) the button.
+    pub fn report(&self) -> GestureReport<D> {
+        GestureReport {
+            clicks: self.clicks(),
+            holds: self.holds(),
+            held_time: self.held_time(),
+            current_hold: self.current_holding_time(),
+        }
+    }
+
+    /// Returns the raw counters and the current state at once, e.g. to log them when a gesture misfires.
+    pub fn diagnostics(&self) -> Diagnostics<D> {
+        Diagnostics {
+            clicks: self.clicks(),
+            holds: self.holds(),
+            raw_clicks: self.raw_clicks(),
+            raw_holds: self.raw_holds(),
+            state: self.state_kind(),
+            time_in_state: self.elapsed_in_state(),
+        }
+    }
+
+    /// Returns [true] only on the tick the first press of a streak is debounced,
+    /// without waiting for the release timeout. Use it for latency-critical actions, e.g. a shutter.
+    pub fn first_press_confirmed(&self) -> bool {
+        matches!(self.event, Some(ButtonEvent::Press)) && self.clicks == 1 && self.holds == 0
+    }
+
+    /// Returns [true] only on the tick the gesture is finished, i.e. the release timeout has just passed.
+    ///
+    /// This is the natural moment to read [clicks](Self::clicks) and [holds](Self::holds) exactly once.
+    pub fn is_settled(&self) -> bool {
+        matches!(self.event, Some(ButtonEvent::Settled { .. }))
+    }
+
     /// Returns holding duration before the last release.
     /// Returns [None] if the button is still being held, not released or was not held at all.
     pub fn held_time(&self) -> Option<D> {
@@ -182,71 +735,540 @@ where
         }
     }
 
+    /// Returns the time spent in the current state, [None] for [Released](State::Released) and [Unknown](State::Unknown).
+    ///
+    /// The time is measured from the instant stored in the state, see [State::instant],
+    /// e.g. [Pressed](State::Pressed) and [Held](State::Held) keep the instant of the press.
+    pub fn elapsed_in_state(&self) -> Option<D> {
+        self.state.instant().map(|instant| self.elapsed(instant))
+    }
+
+    /// Returns the time left in the [release](ButtonConfig::release) window while the button is [Up](State::Up),
+    /// e.g. for a "click again to..." countdown. Clamps to zero once the window has passed.
+    /// Returns [None] in any other state.
+    pub fn click_window_remaining(&self) -> Option<D>
+    where
+        D: Sub<D, Output = D>,
+    {
+        match &self.state {
+            State::Up(since) => {
+                let elapsed = self.elapsed(since);
+                if elapsed < self.config.release {
+                    Some(self.config.release.clone() - elapsed)
+                } else {
+                    Some(D::default())
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the time since the button was [started](Self::start) or first ticked, e.g. to timestamp gestures in logs.
+    /// Returns zero before the first tick of a button created with [new](Self::new).
+    pub fn uptime(&self) -> D {
+        self.started
+            .as_ref()
+            .map(|started| self.elapsed(started))
+            .unwrap_or_default()
+    }
+
+    /// Returns [true] if the current press is still within the [debounce](ButtonConfig::debounce) window,
+    /// e.g. to show "still debouncing" while tuning the debounce time.
+    pub fn is_bouncing(&self) -> bool {
+        match &self.state {
+            State::Down(since) => self.elapsed(since) < self.config.debounce,
+            _ => false,
+        }
+    }
+
     /// Returns current holding duration.
     /// Returns [None] if the button is not being held.
     pub fn current_holding_time(&self) -> Option<D> {
         if let State::Held(dur) = &self.state {
-            Some(dur.elapsed())
+            Some(self.elapsed(dur))
         } else {
             None
         }
     }
 
+    /// Returns [true] if the finished gesture included a hold, i.e. [held_time](Self::held_time) is known.
+    /// Pairs with [is_clicked](Self::is_clicked) to tell a click from a long press until [reset](Self::reset).
+    pub fn was_held(&self) -> bool {
+        self.held_time().is_some()
+    }
+
+    /// Returns [true] if the finished hold, see [held_time](Self::held_time), is longer than `duration`.
+    pub fn held_longer_than(&self, duration: &D) -> bool {
+        self.held_time().is_some_and(|held| held > *duration)
+    }
+
+    /// Returns [true] if the live hold, see [current_holding_time](Self::current_holding_time), is longer than `duration`.
+    pub fn holding_longer_than(&self, duration: &D) -> bool {
+        self.current_holding_time()
+            .is_some_and(|held| held > *duration)
+    }
+
+    /// Returns [true] once after a hold ends, i.e. the button is released after being held.
+    ///
+    /// Combine it with [holds_reached](Self::holds_reached) for "hold twice to confirm" gestures.
+    pub fn hold_completed(&mut self) -> bool {
+        core::mem::take(&mut self.hold_ended)
+    }
+
+    /// Returns [true] once when a hold starts, while the button is still down, e.g. to pop a context menu.
+    ///
+    /// The one-shot is dropped on release if it was not taken during the hold.
+    pub fn long_press_triggered(&mut self) -> bool {
+        core::mem::take(&mut self.hold_started)
+    }
+
+    /// Returns [true] if at least `n` holds happened in the current streak, including a live one.
+    /// Unlike [holds](Self::holds), it does not wait for the release timeout.
+    pub const fn holds_reached(&self, n: usize) -> bool {
+        self.holds >= n
+    }
+
+    /// Returns the live holding duration while the button is held,
+    /// or the duration of the last hold in the streak afterwards.
+    /// Returns [None] if the button was not held or after [reset](Self::reset).
+    pub fn effective_hold_time(&self) -> Option<D> {
+        self.current_holding_time().or_else(|| self.held.clone())
+    }
+
+    /// Returns the button configuration.
+    pub const fn config(&self) -> &ButtonConfig<D> {
+        &self.config
+    }
+
+    /// Returns a copy of the button configuration, e.g. to create a sibling button with the same tuning.
+    pub fn config_cloned(&self) -> ButtonConfig<D> {
+        self.config.clone()
+    }
+
+    /// Sets the [debounce](ButtonConfig::debounce) time, if the config stays [valid](ButtonConfig::validate).
+    pub fn set_debounce(&mut self, debounce: D) -> Result<(), ConfigError> {
+        self.set_timing(|config| config.debounce = debounce)
+    }
+
+    /// Sets the [release](ButtonConfig::release) time, if the config stays [valid](ButtonConfig::validate).
+    pub fn set_release(&mut self, release: D) -> Result<(), ConfigError> {
+        self.set_timing(|config| config.release = release)
+    }
+
+    /// Sets the [hold](ButtonConfig::hold) time, if the config stays [valid](ButtonConfig::validate).
+    pub fn set_hold(&mut self, hold: D) -> Result<(), ConfigError> {
+        self.set_timing(|config| config.hold = hold)
+    }
+
+    /// Applies the change to a copy of the config and keeps it only if it is valid.
+    fn set_timing(&mut self, change: impl FnOnce(&mut ButtonConfig<D>)) -> Result<(), ConfigError> {
+        let mut config = self.config.clone();
+        change(&mut config);
+        config.validate()?;
+        self.config = config;
+        Ok(())
+    }
+
+    /// Returns the button configuration for runtime tuning, e.g. a sensitivity slider.
+    ///
+    /// New timings apply from the next [tick](Self::tick). If [mode](ButtonConfig::mode) is changed,
+    /// the next tick restarts the button from [Unknown](State::Unknown) and clears the counters.
+    pub fn config_mut(&mut self) -> &mut ButtonConfig<D> {
+        self.prev_mode.get_or_insert(self.config.mode);
+        &mut self.config
+    }
+
+    /// Returns the time between the previous and the most recent press edges of the current or the last streak,
+    /// e.g. to calibrate the [release](ButtonConfig::release) window.
+    /// Returns [None] if the streak has a single press.
+    pub fn inter_click_time(&self) -> Option<D> {
+        self.inter_click.clone()
+    }
+
+    /// Returns the number of auto-repeats in the current or the last hold, see [repeat](ButtonConfig::repeat).
+    pub const fn repeats(&self) -> usize {
+        self.repeats
+    }
+
+    /// Returns [true] once per [autofire_interval](ButtonConfig::autofire_interval) while the button is pressed,
+    /// starting right on the debounced press, e.g. for autofire in games.
+    /// Returns [false] if autofire is disabled or the button is [paused](Self::pause).
+    ///
+    /// Unlike [repeats](Self::repeats), which counts repeats during a hold, it is a one-shot polled from the main loop:
+    /// intervals missed between two calls fire once.
+    pub fn fire(&mut self) -> bool {
+        if self.paused {
+            return false;
+        }
+        let Some(interval) = &self.config.autofire_interval else {
+            return false;
+        };
+        let (State::Pressed(press) | State::Held(press)) = &self.state else {
+            return false;
+        };
+
+        let now = self.clock.now();
+        let due = match &self.last_fire {
+            Some(last) => {
+                let since_last = Self::since(&self.clock, &now, last);
+                // A fire of an earlier press is compared by elapsed time rather than by instant to survive wrapping
+                since_last > Self::since(&self.clock, &now, press) || since_last >= *interval
+            }
+            None => true,
+        };
+        if due {
+            self.last_fire = Some(now);
+        }
+        due
+    }
+
+    /// Returns the current auto-repeat interval, taking [repeat_accel](ButtonConfig::repeat_accel) into account.
+    /// Returns [None] if the button is not held or auto-repeat is disabled.
+    pub fn repeat_interval_now(&self) -> Option<D> {
+        if !self.state.is_held() {
+            return None;
+        }
+        let interval = self.config.repeat.clone()?;
+        Some(match &self.config.repeat_accel {
+            Some((after, floor)) if self.repeats >= *after && *floor < interval => floor.clone(),
+            _ => interval,
+        })
+    }
+
     /// Returns current button state.
     pub const fn raw_state(&self) -> &State<I> {
         &self.state
     }
 
+    /// Returns the variant of the current button state, see [StateKind].
+    pub const fn state_kind(&self) -> StateKind {
+        self.state.kind()
+    }
+
+    /// Saves the counters, the state and the config, e.g. before deep sleep, see [from_snapshot](Button::from_snapshot).
+    pub fn snapshot(&self) -> Snapshot<D> {
+        Snapshot {
+            clicks: self.clicks,
+            holds: self.holds,
+            state: self.state_kind(),
+            config: self.config.clone(),
+        }
+    }
+
+    /// Forces the button into the given state, e.g. to restore [Released](State::Released) after a wake up
+    /// instead of starting from [Unknown](State::Unknown).
+    ///
+    /// Counters are left untouched. Be careful: a state that does not match the pin level
+    /// or a stale instant can desync the state machine and produce spurious clicks or holds.
+    pub fn set_state(&mut self, state: State<I>) {
+        self.release_bounce = None;
+        self.state = state;
+    }
+
     /// Returns current amount of clicks, ignoring release timeout.
     pub const fn raw_clicks(&self) -> usize {
         self.clicks
     }
 
     /// Returns current amount of holds (how many times the button was held), ignoring release timeout.
+    /// Includes the hold in progress, see [holds](Self::holds) for the finished streak.
     pub const fn raw_holds(&self) -> usize {
         self.holds
     }
 
+    /// Sets the current amount of clicks, e.g. for a custom [StateMachine] counting clicks its own way.
+    pub fn set_clicks(&mut self, clicks: usize) {
+        self.clicks = clicks;
+        self.click_counted = false;
+    }
+
+    /// Sets the current amount of holds, e.g. for a custom [StateMachine].
+    pub fn set_holds(&mut self, holds: usize) {
+        self.holds = holds;
+    }
+
+    /// Sets the duration of the last hold reported by [held_time](Self::held_time) and [ButtonEvent::HoldEnd],
+    /// e.g. for a custom [StateMachine].
+    pub fn set_held(&mut self, held: Option<D>) {
+        self.held = held;
+    }
+
     /// Updates button state.
     /// Call as frequently as you can, ideally in a loop in separate thread or interrupt.
+    /// Does nothing while the button is [paused](Self::pause).
     pub fn tick(&mut self) {
+        self.update();
+    }
+
+    /// Returns the (old, new) state variants if the last tick has changed the state, e.g. for a live state diagram.
+    /// Returns [None] if the state stayed the same.
+    pub const fn last_change(&self) -> Option<(StateKind, StateKind)> {
+        self.last_change
+    }
+
+    /// Updates button state like [tick](Self::tick), returns [true] if the state variant has changed,
+    /// e.g. to redraw only on change.
+    pub fn tick_changed(&mut self) -> bool {
+        let prev = self.state.clone();
+        self.update();
+        Self::is_transition(&prev, &self.state)
+    }
+
+    /// Updates button state like [tick](Self::tick) and calls `f` with (old, new) state if the state has changed.
+    ///
+    /// Use it for capturing closures, e.g. collecting telemetry, see also [on_transition](Self::on_transition).
+    pub fn tick_with(&mut self, mut f: impl FnMut(&State<I>, &State<I>)) {
+        let prev = self.state.clone();
+        self.update();
+        if Self::is_transition(&prev, &self.state) {
+            f(&prev, &self.state);
+        }
+    }
+
+    /// Sets a function called with (old, new) state on every state change inside [tick](Self::tick),
+    /// [process](Self::process) and [replay](Self::replay). This is lower-level than [ButtonEvent],
+    /// useful to debug why a gesture misfires. [None] removes the hook.
+    ///
+    /// A plain function pointer keeps the button `no_std`, [Clone] and [Debug].
+    /// For capturing closures use [tick_with](Self::tick_with).
+    pub fn on_transition(&mut self, hook: Option<TransitionHook<I>>) {
+        self.transition_hook = hook;
+    }
+
+    /// Returns an endless iterator ticking the button once per item, for a clean polling loop.
+    ///
+    /// ```ignore
+    /// for event in button.events().flatten() {
+    ///     match event {
+    ///         ButtonEvent::Press => println!("Press"),
+    ///         ButtonEvent::Settled { clicks, .. } => println!("{clicks} clicks"),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// ```
+    pub fn events(&mut self) -> Events<'_, P, I, D, C, K, S> {
+        Events::new(self)
+    }
+
+    /// Updates button state and [resets](Self::reset) it right away,
+    /// so a finished gesture is reported exactly once and can not latch.
+    ///
+    /// Returns the event produced by this update, if any.
+    #[must_use]
+    pub fn tick_and_reset(&mut self) -> Option<ButtonEvent<D>> {
+        let event = self.update();
+        self.reset();
+        event
+    }
+
+    /// Updates button state, returns the produced event.
+    fn update(&mut self) -> Option<ButtonEvent<D>> {
+        let now = self.clock.now();
+        let pressed = self.is_pin_pressed();
+        self.update_at(now, pressed)
+    }
+
+    /// Updates button state with the given time and pin status, returns the produced event.
+    fn update_at(&mut self, now: I, pressed: bool) -> Option<ButtonEvent<D>> {
+        if self.started.is_none() {
+            self.started = Some(now.clone());
+        }
+        let start = self.state.kind();
+        self.event = if self.paused {
+            None
+        } else if self.is_tick_gap(&now) {
+            self.restart();
+            None
+        } else {
+            if self
+                .prev_mode
+                .take()
+                .is_some_and(|mode| mode != self.config.mode)
+            {
+                self.restart();
+            }
+            if self.config.auto_reset {
+                self.reset();
+            }
+            let prev = self.state.clone();
+            let was_pending = self.hold_pending;
+            S::step(self, now, pressed);
+            if let Some(hook) = self.transition_hook {
+                if Self::is_transition(&prev, &self.state) {
+                    hook(&prev, &self.state);
+                }
+            }
+            self.event(&prev, was_pending)
+        };
+        let end = self.state.kind();
+        self.last_change = (start != end).then_some((start, end));
+
+        if let Some(event) = &self.event {
+            self.pending = Some(event.clone());
+            #[cfg(feature = "heapless")]
+            self.record(event.clone());
+        }
+
+        self.event.clone()
+    }
+
+    /// Takes the last event produced by a tick, so it is handled once even if it is read ticks later.
+    ///
+    /// A single event is kept: an event not taken before the next one is produced is dropped.
+    /// See `history` under the `heapless` feature to keep more.
+    pub fn next_event(&mut self) -> Option<ButtonEvent<D>> {
+        self.pending.take()
+    }
+
+    /// Returns the event [next_event](Self::next_event) would take, without taking it.
+    pub const fn peek_event(&self) -> Option<&ButtonEvent<D>> {
+        self.pending.as_ref()
+    }
+
+    /// Handles the edge [taken](EdgeLatch::take) from an interrupt's [EdgeLatch] and the pending timeouts,
+    /// using the last reported pin level instead of reading the pin.
+    ///
+    /// Call it regularly from the main loop, even if there are no new edges.
+    /// Edges processed while [paused](Self::pause) are dropped.
+    pub fn process(&mut self, latch: EdgeLatch<I>) {
+        let Some(level) = latch.level() else {
+            return;
+        };
+        let pressed = self.is_level_pressed(level);
+
+        if let Some(edge) = latch.edge() {
+            self.update_at(edge, pressed);
+        }
+        let now = self.clock.now();
+        self.update_at(now, pressed);
+    }
+
+    /// Updates button state with a burst of `(instant, pin level)` samples taken since the last tick,
+    /// instead of reading the pin and the clock once.
+    ///
+    /// Useful when [tick](Self::tick) can only be called rarely, but the pin can be sampled in bursts:
+    /// fast clicks between two ticks are not collapsed. Samples must be in chronological order.
+    pub fn tick_sampled(&mut self, samples: &[(I, bool)]) {
+        for (now, level) in samples {
+            let pressed = self.is_level_pressed(*level);
+            self.update_at(now.clone(), pressed);
+        }
+    }
+
+    /// Feeds recorded `(instant, pin level)` samples to the button instead of reading the pin and the clock,
+    /// e.g. a logic analyzer trace. Returns the produced events.
+    ///
+    /// Samples are processed lazily, as the returned iterator is consumed.
+    pub fn replay<'a, It>(&'a mut self, samples: It) -> impl Iterator<Item = ButtonEvent<D>> + 'a
+    where
+        It: IntoIterator<Item = (I, bool)>,
+        It::IntoIter: 'a,
+    {
+        samples.into_iter().filter_map(move |(now, level)| {
+            let pressed = self.is_level_pressed(level);
+            self.update_at(now, pressed)
+        })
+    }
+
+    /// Records the tick instant, returns [true] if the time since the previous tick
+    /// exceeds [max_tick_gap](ButtonConfig::max_tick_gap).
+    fn is_tick_gap(&mut self, now: &I) -> bool {
+        let last = self.last_tick.replace(now.clone());
+        match (&self.config.max_tick_gap, last) {
+            (Some(gap), Some(last)) => Self::since(&self.clock, now, &last) > *gap,
+            _ => false,
+        }
+    }
+
+    /// Saves the event into the history, dropping the oldest one if it is full.
+    #[cfg(feature = "heapless")]
+    fn record(&mut self, event: ButtonEvent<D>) {
+        if K == 0 {
+            return;
+        }
+        if self.history.is_full() {
+            self.history.remove(0);
+        }
+        // Can not fail, there is a free slot
+        let _ = self.history.push(event);
+    }
+
+    /// Advances the state machine.
+    /// The transition logic of [StandardMachine].
+    fn step(&mut self, now: I, pressed: bool) {
+        let clock = &self.clock;
+        let since = |instant: &I| Self::since(clock, &now, instant);
+
         match self.state.clone() {
-            State::Unknown if self.is_pin_pressed() => {
-                self.clicks += 1;
-                self.state = State::Down(I::now());
+            State::Unknown if pressed && self.config.ignore_initial_press => {
+                // waiting for the release
+            }
+            State::Unknown if pressed => {
+                self.count_click();
+                self.inter_click = None;
+                self.last_press = Some(now.clone());
+                self.state = State::Down(now.clone());
             }
-            State::Unknown if self.is_pin_released() => self.state = State::Released,
+            State::Unknown => self.state = State::Released,
 
             State::Down(elapsed) => {
-                if self.is_pin_pressed() {
-                    if elapsed.elapsed() >= self.config.debounce {
+                if pressed {
+                    let pressed_for = since(&elapsed);
+                    if pressed_for >= self.config.debounce
+                        && self
+                            .config
+                            .min_press
+                            .as_ref()
+                            .is_none_or(|min| pressed_for >= *min)
+                    {
+                        self.total_presses = self.total_presses.saturating_add(1);
                         self.state = State::Pressed(elapsed.clone());
                     } else {
                         // debounce
                     }
                 } else {
-                    self.state = State::Released;
+                    // A glitch or a press shorter than min_press, not a click
+                    self.uncount_click();
+                    self.state = if self.clicks > 0 || self.holds > 0 {
+                        // Back to the streak, the release timeout starts over
+                        State::Up(now.clone())
+                    } else {
+                        State::Released
+                    };
                 }
             }
             State::Pressed(elapsed) => {
-                if self.is_pin_pressed() {
-                    if elapsed.elapsed() >= self.config.hold {
-                        // Do not count a click that leads to a hold
-                        self.clicks -= 1;
-                        self.holds += 1;
+                if pressed {
+                    self.release_bounce = None;
+                    if since(&elapsed) >= self.config.hold {
+                        // With hold_must_exceed the hold is counted once it is exceeded
+                        self.hold_pending = self.config.hold_must_exceed.is_some();
+                        if !self.hold_pending {
+                            self.count_hold();
+                            self.hold_started = true;
+                        }
+                        self.repeats = 0;
+                        self.last_repeat = Some(now.clone());
                         self.state = State::Held(elapsed.clone());
                     } else {
                         // holding
                     }
+                } else if self.is_release_debounced(&now) {
+                    self.release_bounce = None;
+                    self.latched = !self.latched;
+                    self.last_click = Some(now.clone());
+                    self.state = State::Up(now.clone())
                 } else {
-                    self.state = State::Up(I::now())
+                    // release debounce
                 }
             }
             State::Up(elapsed) => {
-                if elapsed.elapsed() < self.config.release {
-                    if self.is_pin_pressed() {
-                        self.clicks += 1;
-                        self.state = State::Down(I::now());
+                if since(&elapsed) < self.config.release {
+                    if pressed && !self.is_click_too_soon(&now) {
+                        self.inter_click = self.last_press.as_ref().map(since);
+                        self.count_click();
+                        self.last_press = Some(now.clone());
+                        self.state = State::Down(now.clone());
                     } else {
                         // waiting for the release timeout
                     }
@@ -255,27 +1277,203 @@ where
                 }
             }
 
-            State::Released if self.is_pin_pressed() => {
-                self.clicks += 1;
+            State::Released if pressed && !self.is_click_too_soon(&now) => {
+                self.count_click();
                 self.held = None;
-                self.state = State::Down(I::now());
+                self.inter_click = None;
+                self.last_press = Some(now.clone());
+                self.state = State::Down(now.clone());
             }
             State::Held(elapsed) => {
-                if self.is_pin_released() {
-                    // TODO: save prior held time?
-                    self.held = Some(elapsed.elapsed());
-                    self.state = State::Up(I::now());
+                if pressed {
+                    self.release_bounce = None;
+                    if let Some(interval) = self.repeat_interval_now() {
+                        if self
+                            .last_repeat
+                            .as_ref()
+                            .is_some_and(|last| since(last) >= interval)
+                        {
+                            self.repeats = self.repeats.saturating_add(1);
+                            self.last_repeat = Some(now.clone());
+                        }
+                    }
+                    if self.hold_pending
+                        && self
+                            .config
+                            .hold_must_exceed
+                            .as_ref()
+                            .is_some_and(|min| since(&elapsed) > *min)
+                    {
+                        self.hold_pending = false;
+                        self.count_hold();
+                        self.hold_started = true;
+                    }
+                } else if self.is_release_debounced(&now) {
+                    self.release_bounce = None;
+                    if self.hold_pending {
+                        // Released before exceeding hold_must_exceed, a click after all
+                        self.latched = !self.latched;
+                        self.last_click = Some(now.clone());
+                    } else {
+                        // TODO: save prior held time?
+                        self.end_hold(&now, &elapsed);
+                    }
+                    self.state = State::Up(now.clone());
                 } else {
-                    // holding
+                    // release debounce
                 }
             }
             _ => {}
         }
     }
 
+    /// Returns [true] if a press at `now` comes within [min_click_interval](ButtonConfig::min_click_interval)
+    /// after the release of the last click.
+    fn is_click_too_soon(&self, now: &I) -> bool {
+        match (&self.config.min_click_interval, &self.last_click) {
+            (Some(interval), Some(last)) => Self::since(&self.clock, now, last) < *interval,
+            _ => false,
+        }
+    }
+
+    /// Returns [true] if the state variant differs, instants of the same variant are not compared.
+    fn is_transition(old: &State<I>, new: &State<I>) -> bool {
+        core::mem::discriminant(old) != core::mem::discriminant(new)
+    }
+
+    /// Returns the time passed from `instant` to `now` measured by the clock, see [ClockSource::checked_elapsed_since],
+    /// saturating at zero if the clock went backwards.
+    fn since(clock: &C, now: &I, instant: &I) -> D {
+        clock
+            .checked_elapsed_since(instant, now)
+            .unwrap_or_default()
+    }
+
+    /// Returns the time passed from `instant` to now.
+    fn elapsed(&self, instant: &I) -> D {
+        Self::since(&self.clock, &self.clock.now(), instant)
+    }
+
+    /// Counts a new press as a click, unless [max_clicks](ButtonConfig::max_clicks) is reached.
+    /// Saturates at [usize::MAX], which is reachable by mashing on 16-bit targets.
+    fn count_click(&mut self) {
+        self.click_counted =
+            self.clicks < usize::MAX && self.config.max_clicks.is_none_or(|max| self.clicks < max);
+        if self.click_counted {
+            self.clicks += 1;
+        }
+    }
+
+    /// Returns the clicks limited by [click_ceiling](ButtonConfig::click_ceiling).
+    fn reported_clicks(&self) -> usize {
+        self.config
+            .click_ceiling
+            .map_or(self.clicks, |ceiling| self.clicks.min(ceiling))
+    }
+
+    /// Counts the current press as a hold instead of a click.
+    fn count_hold(&mut self) {
+        // Do not count a click that leads to a hold
+        self.uncount_click();
+        if self.holds == 0 {
+            self.clicks_before_hold = self.clicks;
+        }
+        if self.config.click_hold_policy == ClickHoldPolicy::HoldCancelsClicks {
+            self.clicks = 0;
+        }
+        self.holds = self.holds.saturating_add(1);
+    }
+
+    /// Finishes the hold of the press at `press` released at `now`.
+    fn end_hold(&mut self, now: &I, press: &I) {
+        self.held = Some(Self::since(&self.clock, now, press));
+        self.hold_ended = true;
+        self.hold_started = false;
+    }
+
+    /// Takes back the click of the current press, if it was counted.
+    fn uncount_click(&mut self) {
+        if self.click_counted {
+            self.click_counted = false;
+            self.clicks = self.clicks.saturating_sub(1);
+        }
+    }
+
+    /// Returns the event corresponding to the transition from the `prev` state,
+    /// `was_pending` is the [hold_must_exceed](ButtonConfig::hold_must_exceed) state before the transition.
+    fn event(&self, prev: &State<I>, was_pending: bool) -> Option<ButtonEvent<D>> {
+        match (prev, &self.state) {
+            (State::Down(_), State::Pressed(_)) => Some(ButtonEvent::Press),
+            (State::Pressed(_), State::Held(_)) if !self.hold_pending => {
+                Some(ButtonEvent::HoldStart)
+            }
+            // hold_must_exceed is exceeded
+            (State::Held(_), State::Held(_)) if was_pending && !self.hold_pending => {
+                Some(ButtonEvent::HoldStart)
+            }
+            (State::Pressed(_), State::Up(_)) => Some(ButtonEvent::Release),
+            (State::Held(_), State::Up(_)) if self.hold_pending => Some(ButtonEvent::Release),
+            (State::Held(_), State::Up(_)) => self.held.clone().map(ButtonEvent::HoldEnd),
+            // A discarded press from the idle state goes `Down -> Released` silently
+            (State::Up(_), State::Released) => Some(ButtonEvent::Settled {
+                clicks: self.reported_clicks(),
+                holds: self.holds,
+                held: self.held.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Reads current pin status, returns [true] if the button pin is pressed without debouncing.
+    ///
+    /// Does not advance the state machine, the configured [Mode] is respected.
+    pub fn read_raw(&mut self) -> bool {
+        self.is_pin_pressed()
+    }
+
+    /// Returns [true] if the last committed state is a debounced press, i.e. [Pressed](State::Pressed) or [Held](State::Held).
+    ///
+    /// Does not read the pin, reflects the state after the last [tick](Self::tick).
+    pub fn read_debounced(&self) -> bool {
+        matches!(self.state, State::Pressed(_) | State::Held(_))
+    }
+
+    /// Returns the debounced level of the button, [true] if pressed, for using the button as a plain debounced input.
+    ///
+    /// It ignores the click and hold layer: the level drops as soon as the release is debounced,
+    /// without waiting for the [release](ButtonConfig::release) timeout. Same as [read_debounced](Self::read_debounced).
+    pub fn debounced_level(&self) -> bool {
+        self.read_debounced()
+    }
+
+    /// Returns [true] if the pin has been released for at least [release_debounce](ButtonConfig::release_debounce).
+    fn is_release_debounced(&mut self, now: &I) -> bool {
+        let Some(debounce) = &self.config.release_debounce else {
+            return true;
+        };
+
+        match &self.release_bounce {
+            Some(bounce) => Self::since(&self.clock, now, bounce) >= *debounce,
+            None => {
+                self.release_bounce = Some(now.clone());
+                false
+            }
+        }
+    }
+
+    /// Returns [true] if the given pin level means pressed, according to the pin
+    /// [active level](PinWrapper::active_level) or [Mode].
+    fn is_level_pressed(&self, level: bool) -> bool {
+        match self.pin.active_level() {
+            Some(active) => level == active,
+            None => level != self.config.mode.is_pullup(),
+        }
+    }
+
     /// Reads current pin status, returns [true] if the button pin is released without debouncing.
     fn is_pin_released(&mut self) -> bool {
-        self.pin.is_high() == self.config.mode.is_pullup()
+        let level = self.pin.is_high();
+        !self.is_level_pressed(level)
     }
 
     /// Reads current pin status, returns [true] if the button pin is pressed without debouncing.
@@ -283,3 +1481,95 @@ where
         !self.is_pin_released()
     }
 }
+
+impl<P, I, D, C, const K: usize> Button<P, I, D, C, K, StandardMachine>
+where
+    P: PinWrapper,
+    I: Sub<I, Output = D> + Clone + PartialOrd,
+    D: Clone + PartialOrd + Default,
+    C: ClockSource<I, D>,
+{
+    /// Updates button state like [tick](Self::tick), unless the pin level has not changed and no timeout is pending,
+    /// i.e. the button is idle [Released](State::Released) or [Held](State::Held) without [repeat](ButtonConfig::repeat).
+    /// The clock is not read then, saving work in power-sensitive polling loops.
+    /// Never skips a tick if [max_tick_gap](ButtonConfig::max_tick_gap) is set.
+    ///
+    /// Available for [StandardMachine] only, since it relies on its transitions.
+    pub fn tick_if_changed(&mut self) {
+        let pressed = self.is_pin_pressed();
+        let idle = match self.state {
+            State::Released => !pressed,
+            // A bounce during the hold must be cleared and hold_must_exceed checked by a pressed tick
+            State::Held(_) => {
+                pressed
+                    && self.config.repeat.is_none()
+                    && self.release_bounce.is_none()
+                    && !self.hold_pending
+            }
+            _ => false,
+        };
+        if idle
+            && self.event.is_none()
+            && self.prev_mode.is_none()
+            && self.config.max_tick_gap.is_none()
+        {
+            self.last_change = None;
+            return;
+        }
+        let now = self.clock.now();
+        self.update_at(now, pressed);
+    }
+}
+
+impl<P, I, C, const K: usize, S> Button<P, I, Duration, C, K, S>
+where
+    P: PinWrapper,
+    I: Sub<I, Output = Duration> + Clone + PartialOrd,
+    C: ClockSource<I, Duration>,
+    S: StateMachine,
+{
+    /// Returns how far the live press is towards the [hold](ButtonConfig::hold) time, from 0.0 to 1.0,
+    /// e.g. for a "hold to confirm" ring filling up. Stays 1.0 while held.
+    /// Returns [None] if the button is not pressed.
+    pub fn hold_progress(&self) -> Option<f32> {
+        let (State::Pressed(since) | State::Held(since)) = &self.state else {
+            return None;
+        };
+        if self.config.hold.is_zero() {
+            return Some(1.0);
+        }
+        let pressed = self.elapsed(since);
+        Some((pressed.as_secs_f32() / self.config.hold.as_secs_f32()).min(1.0))
+    }
+}
+
+#[cfg(feature = "embedded_hal")]
+impl<P, I, D, C, const K: usize, S> Button<FalliblePin<P>, I, D, C, K, S>
+where
+    P: embedded_hal::digital::InputPin,
+{
+    /// Returns the error of the last pin read, [None] if it succeeded.
+    pub const fn last_pin_error(&self) -> Option<&P::Error> {
+        self.pin.last_error()
+    }
+}
+
+impl<P, I, D, C, const K: usize, S> core::fmt::Debug for Button<P, I, D, C, K, S>
+where
+    I: core::fmt::Debug,
+    D: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut debug = f.debug_struct("Button");
+        if !self.name.is_empty() {
+            debug.field("name", &self.name);
+        }
+        debug
+            .field("state", &self.state)
+            .field("clicks", &self.clicks)
+            .field("holds", &self.holds)
+            .field("held", &self.held)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
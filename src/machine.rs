@@ -0,0 +1,43 @@
+use core::ops::Sub;
+
+use crate::{Button, ClockSource, PinWrapper};
+
+/// The transition logic of a [Button], e.g. to swap the click and hold algorithm
+/// while reusing the pin and time plumbing.
+///
+/// A custom machine drives the button through [Button::raw_state] and [Button::set_state],
+/// [events](crate::ButtonEvent) are derived from the state transitions as usual.
+/// It keeps the counters with [Button::set_clicks], [Button::set_holds] and [Button::set_held],
+/// read back by [Button::raw_clicks] and [Button::raw_holds], so [clicks](Button::clicks), [holds](Button::holds)
+/// and the [Settled](crate::ButtonEvent::Settled) event report them as usual.
+pub trait StateMachine: Sized {
+    /// Advances the button given the current time and whether the pin is pressed.
+    fn step<P, I, D, C, const K: usize>(
+        button: &mut Button<P, I, D, C, K, Self>,
+        now: I,
+        pressed: bool,
+    ) where
+        P: PinWrapper,
+        I: Sub<I, Output = D> + Clone + PartialOrd,
+        D: Clone + PartialOrd + Default,
+        C: ClockSource<I, D>;
+}
+
+/// The default click and hold state machine, see [State](crate::State) for the diagram.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StandardMachine;
+
+impl StateMachine for StandardMachine {
+    fn step<P, I, D, C, const K: usize>(
+        button: &mut Button<P, I, D, C, K, Self>,
+        now: I,
+        pressed: bool,
+    ) where
+        P: PinWrapper,
+        I: Sub<I, Output = D> + Clone + PartialOrd,
+        D: Clone + PartialOrd + Default,
+        C: ClockSource<I, D>,
+    {
+        button.step(now, pressed);
+    }
+}
@@ -1,3 +1,7 @@
+use core::{ops::Sub, time::Duration};
+
+use crate::{ClockSource, GlobalClock, InstantProvider};
+
 /// An abstraction over different switching APIs.
 pub trait PinWrapper {
     /// Is source on?
@@ -7,6 +11,226 @@ pub trait PinWrapper {
     fn is_low(&mut self) -> bool {
         !self.is_high()
     }
+
+    /// The level of a pressed button, overriding the [Mode](crate::Mode) of the config,
+    /// e.g. for mixed-polarity hardware under one config. [None] means the mode is used.
+    fn active_level(&self) -> Option<bool> {
+        None
+    }
+}
+
+/// Borrows a pin for a button without moving it, e.g. `Button::new(PinRef(&mut pin), config)`.
+///
+/// `&mut pin` itself cannot be a [PinWrapper]: `embedded_hal` already implements `InputPin` for `&mut T`,
+/// so it would get two implementations.
+#[derive(Debug)]
+pub struct PinRef<'a, T: ?Sized>(pub &'a mut T);
+
+impl<T: PinWrapper + ?Sized> PinWrapper for PinRef<'_, T> {
+    fn is_high(&mut self) -> bool {
+        self.0.is_high()
+    }
+
+    fn is_low(&mut self) -> bool {
+        self.0.is_low()
+    }
+
+    fn active_level(&self) -> Option<bool> {
+        self.0.active_level()
+    }
+}
+
+/// A pin read by a closure returning its level, handy for prototypes and tests.
+///
+/// A bare closure cannot be a [PinWrapper]: the compiler cannot rule out a closure type implementing `InputPin`,
+/// so an implementation for every `FnMut() -> bool` would conflict with the `InputPin` one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FnPin<F>(pub F);
+
+impl<F> PinWrapper for FnPin<F>
+where
+    F: FnMut() -> bool,
+{
+    fn is_high(&mut self) -> bool {
+        (self.0)()
+    }
+}
+
+/// An abstraction over a whole port read at once, e.g. an I2C port expander.
+pub trait PortWrapper {
+    /// Reads all pins of the port, bit `n` is the level of pin `n`.
+    fn read(&mut self) -> u32;
+}
+
+/// A single pin of a port, holding the level from the last port read.
+///
+/// Used by [BankButtons](crate::BankButtons) to extract individual bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortPin {
+    bit: u8,
+    level: bool,
+}
+
+impl PortPin {
+    /// Creates a new [PortPin] for the given bit.
+    pub const fn new(bit: u8) -> Self {
+        Self { bit, level: false }
+    }
+
+    /// Returns the bit of this pin in the port.
+    pub const fn bit(&self) -> u8 {
+        self.bit
+    }
+
+    /// Updates the level from the whole port value.
+    pub fn update(&mut self, port: u32) {
+        self.level = port >> self.bit & 1 == 1;
+    }
+}
+
+impl PinWrapper for PortPin {
+    fn is_high(&mut self) -> bool {
+        self.level
+    }
+}
+
+/// An analog source, e.g. an ADC reading a capacitive or hall-effect sensor, compared against thresholds.
+///
+/// The pin becomes high once the sample reaches the `high` threshold and stays high
+/// until it drops below the `low` threshold, so the noise between them does not cause chatter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThresholdPin<F> {
+    sample: F,
+    low: u16,
+    high: u16,
+    level: bool,
+}
+
+impl<F> ThresholdPin<F>
+where
+    F: FnMut() -> u16,
+{
+    /// Creates a new [ThresholdPin] without hysteresis.
+    pub const fn new(sample: F, threshold: u16) -> Self {
+        Self::with_hysteresis(sample, threshold, threshold)
+    }
+
+    /// Creates a new [ThresholdPin] with separate `low` and `high` thresholds.
+    ///
+    /// `low` is expected to be less or equal to `high`.
+    pub const fn with_hysteresis(sample: F, low: u16, high: u16) -> Self {
+        Self {
+            sample,
+            low,
+            high,
+            level: false,
+        }
+    }
+}
+
+impl<F> PinWrapper for ThresholdPin<F>
+where
+    F: FnMut() -> u16,
+{
+    fn is_high(&mut self) -> bool {
+        let sample = (self.sample)();
+        if self.level {
+            self.level = sample >= self.low;
+        } else {
+            self.level = sample >= self.high;
+        }
+        self.level
+    }
+}
+
+/// A pin debounced on its own, independently of the [Button](crate::Button) state machine,
+/// e.g. to share one debounced source between several consumers.
+///
+/// The level changes only after the inner pin keeps the new level for `rise` (to high) or `fall` (to low) time,
+/// set the [Button](crate::Button) debounce to zero to debounce at the pin level only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Debounced<P, I, D = Duration, C = GlobalClock> {
+    pin: P,
+    rise: D,
+    fall: D,
+    level: bool,
+    /// The instant the inner pin has first differed from the debounced level.
+    change: Option<I>,
+    clock: C,
+}
+
+impl<P, I, D> Debounced<P, I, D>
+where
+    P: PinWrapper,
+    I: InstantProvider<D> + PartialOrd,
+    D: Clone + PartialOrd,
+{
+    /// Creates a new [Debounced] pin with the same time for both edges.
+    pub fn new(pin: P, debounce: D) -> Self {
+        Self::with_hysteresis(pin, debounce.clone(), debounce)
+    }
+
+    /// Creates a new [Debounced] pin with separate `rise` and `fall` times.
+    pub const fn with_hysteresis(pin: P, rise: D, fall: D) -> Self {
+        Self::with_clock(pin, rise, fall, GlobalClock)
+    }
+}
+
+impl<P, I, D, C> Debounced<P, I, D, C>
+where
+    P: PinWrapper,
+    I: Sub<I, Output = D> + Clone + PartialOrd,
+    D: Clone + PartialOrd,
+    C: ClockSource<I, D>,
+{
+    /// Creates a new [Debounced] pin reading the time from the given [ClockSource].
+    pub const fn with_clock(pin: P, rise: D, fall: D, clock: C) -> Self {
+        Self {
+            pin,
+            rise,
+            fall,
+            level: false,
+            change: None,
+            clock,
+        }
+    }
+
+    /// Returns the inner pin.
+    pub fn into_inner(self) -> P {
+        self.pin
+    }
+}
+
+impl<P, I, D, C> PinWrapper for Debounced<P, I, D, C>
+where
+    P: PinWrapper,
+    I: Sub<I, Output = D> + Clone + PartialOrd,
+    D: Clone + PartialOrd,
+    C: ClockSource<I, D>,
+{
+    fn is_high(&mut self) -> bool {
+        if self.pin.is_high() == self.level {
+            self.change = None;
+            return self.level;
+        }
+
+        let now = self.clock.now();
+        let change = self.change.get_or_insert_with(|| now.clone());
+        let debounce = if self.level { &self.fall } else { &self.rise };
+        if self
+            .clock
+            .checked_elapsed_since(change, &now)
+            .is_some_and(|elapsed| elapsed >= *debounce)
+        {
+            self.level = !self.level;
+            self.change = None;
+        }
+        self.level
+    }
+
+    fn active_level(&self) -> Option<bool> {
+        self.pin.active_level()
+    }
 }
 
 #[cfg(feature = "embedded_hal_old")]
@@ -29,36 +253,129 @@ where
     }
 }
 
+/// An `embedded_hal` pin keeping its last read error instead of silently swallowing it,
+/// e.g. to detect a flaky port expander, see [Button::last_pin_error](crate::Button::last_pin_error).
+///
+/// A failed read is treated as a low level, like the plain `embedded_hal` implementation.
+#[cfg(feature = "embedded_hal")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FalliblePin<P: embedded_hal::digital::ErrorType> {
+    /// An inner pin.
+    pub pin: P,
+    last_error: Option<P::Error>,
+}
+
+#[cfg(feature = "embedded_hal")]
+impl<P: embedded_hal::digital::InputPin> FalliblePin<P> {
+    /// Creates a new [FalliblePin].
+    pub const fn new(pin: P) -> Self {
+        Self {
+            pin,
+            last_error: None,
+        }
+    }
+
+    /// Returns the error of the last read, [None] if it succeeded.
+    pub const fn last_error(&self) -> Option<&P::Error> {
+        self.last_error.as_ref()
+    }
+}
+
+#[cfg(feature = "embedded_hal")]
+impl<P: embedded_hal::digital::InputPin> PinWrapper for FalliblePin<P> {
+    fn is_high(&mut self) -> bool {
+        match self.pin.is_high() {
+            Ok(level) => {
+                self.last_error = None;
+                level
+            }
+            Err(err) => {
+                self.last_error = Some(err);
+                false
+            }
+        }
+    }
+}
+
+/// An `embedded_hal` output pin read back as a button, e.g. an open-drain pin shared with a button.
+///
+/// The level is read by [is_set_high](embedded_hal::digital::StatefulOutputPin::is_set_high),
+/// which reflects the button only if the HAL reads the pad back instead of the output register.
+/// Prefer the pin directly if it implements `InputPin` too.
+/// The wrapper is needed because a pin implementing both traits would otherwise get two implementations.
+/// A failed read is treated as a low level.
+#[cfg(feature = "embedded_hal")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputPinReadback<P>(pub P);
+
+#[cfg(feature = "embedded_hal")]
+impl<P: embedded_hal::digital::StatefulOutputPin> PinWrapper for OutputPinReadback<P> {
+    fn is_high(&mut self) -> bool {
+        self.0.is_set_high().unwrap_or_default()
+    }
+}
+
+/// A Linux userspace GPIO line requested through the character device.
+///
+/// Request the line as an input, e.g. `line.request(LineRequestFlags::INPUT, 0, "button")`.
+#[cfg(feature = "gpio_cdev")]
+#[derive(Debug)]
+pub struct CdevPin(pub gpio_cdev::LineHandle);
+
+#[cfg(feature = "gpio_cdev")]
+impl PinWrapper for CdevPin {
+    fn is_high(&mut self) -> bool {
+        self.0.get_value().map(|v| v != 0).unwrap_or_default()
+    }
+}
+
 #[cfg(all(test, feature = "std"))]
 pub(crate) mod tests {
     use std::{
-        sync::{
-            atomic::{AtomicBool, Ordering},
-            Arc,
-        },
         thread::sleep,
         time::{Duration, Instant},
     };
 
-    use crate::{Button, ButtonConfig, Mode, PinWrapper, State};
+    use crate::{testing, Button, ButtonConfig, ClickHoldPolicy, Mode, PinWrapper, State};
 
     pub const CONFIG: ButtonConfig = ButtonConfig {
         hold: Duration::from_millis(500),
         debounce: Duration::from_micros(700),
         release: Duration::from_millis(30),
+        release_debounce: None,
+        min_press: None,
+        max_clicks: None,
+        click_hold_policy: ClickHoldPolicy::HoldPreservesClicks,
+        auto_reset: false,
+        repeat: None,
+        repeat_accel: None,
+        ignore_initial_press: false,
+        hold_must_exceed: None,
+        click_ceiling: None,
+        max_tick_gap: None,
+        autofire_interval: None,
+        min_click_interval: None,
         mode: Mode::PullDown,
     };
 
-    #[derive(Debug, Default, Clone)]
-    pub struct MockPin(Arc<AtomicBool>);
+    /// The public [MockPin](testing::MockPin) wired for [CONFIG], with the debounced helpers of the tests ticking in real time.
+    #[derive(Debug, Clone)]
+    pub struct MockPin(testing::MockPin);
+
+    impl Default for MockPin {
+        fn default() -> Self {
+            Self(testing::MockPin::new(CONFIG.mode))
+        }
+    }
 
     impl PinWrapper for MockPin {
         fn is_high(&mut self) -> bool {
-            self.0.load(Ordering::SeqCst)
+            self.0.is_high()
         }
     }
 
     impl Button<MockPin, Instant> {
+        /// Press the button pin and tick until the press is debounced.
         pub fn press_button(&mut self) {
             self.pin.press();
             self.tick();
@@ -68,11 +385,13 @@ pub(crate) mod tests {
             self.tick();
         }
 
+        /// Release the button pin and tick once.
         pub fn release_button(&mut self) {
             self.pin.release();
             self.tick();
         }
 
+        /// Press the button, wait for the hold and release it.
         pub fn hold_button(&mut self) {
             self.press_button();
             sleep(CONFIG.hold);
@@ -82,29 +401,36 @@ pub(crate) mod tests {
     }
 
     impl MockPin {
+        /// Press the pin without waiting.
+        pub fn press_raw(&self) {
+            self.0.press();
+        }
+
+        /// Release the pin without waiting.
+        pub fn release_raw(&self) {
+            self.0.release();
+        }
+
         /// Press the pin with debounce.
         pub fn press(&self) {
-            self.0.store(true, Ordering::SeqCst);
+            self.0.press();
             sleep(CONFIG.debounce);
         }
 
         /// Release the pin with debounce.
         pub fn release(&self) {
-            self.0.store(false, Ordering::SeqCst);
+            self.0.release();
             sleep(CONFIG.debounce);
         }
 
         /// Simulate pin state changes corresponding to one full button click with debounce.
         pub fn click(&self) {
-            self.press();
-            self.release();
+            self.0.click(&CONFIG);
         }
 
         /// Simulate pin state changes corresponding to one full button hold with debounce.
         pub fn hold(&self) {
-            self.press();
-            sleep(CONFIG.hold);
-            self.release();
+            self.0.hold(&CONFIG);
         }
     }
 }
@@ -0,0 +1,7 @@
+//! Everything needed to drive a button, including with integer ticks instead of [Duration](core::time::Duration):
+//! `use button_driver::prelude::*;`
+
+pub use crate::{
+    Button, ButtonConfig, ButtonEvent, ClockSource, FnPin, Gesture, GlobalClock, InstantProvider,
+    Mode, PinWrapper, State, StateKind, TickCount,
+};
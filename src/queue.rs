@@ -0,0 +1,97 @@
+use core::{
+    fmt,
+    ops::{Deref, DerefMut, Sub},
+};
+
+use heapless::spsc::Producer;
+
+use crate::{Button, ButtonEvent, ClockSource, PinWrapper, StateMachine};
+
+/// The sending half of an event queue.
+type EventProducer<'a, D, const N: usize> = Producer<'a, ButtonEvent<D>, N>;
+
+/// A [Button] pushing its events into a [heapless::spsc] queue, see [Button::with_producer].
+///
+/// The usual split is ticking in an interrupt and taking the events from the
+/// [Consumer](heapless::spsc::Consumer) in a task, both sides are lock-free.
+///
+/// A `heapless::spsc::Queue<_, N>` holds `N - 1` events. If the consumer falls behind and the queue is full,
+/// new events are dropped and counted, see [dropped](Self::dropped).
+/// Events already in the queue are never overwritten.
+///
+/// Dereferences to the inner [Button], so all the accessors are available as usual.
+pub struct QueuedButton<'a, B, D, const N: usize> {
+    /// An inner button.
+    pub button: B,
+    /// The sending half of the event queue.
+    pub producer: EventProducer<'a, D, N>,
+    /// Events dropped because the queue was full.
+    dropped: usize,
+}
+
+impl<'a, P, I, D, C, const K: usize, S, const N: usize>
+    QueuedButton<'a, Button<P, I, D, C, K, S>, D, N>
+where
+    P: PinWrapper,
+    I: Sub<I, Output = D> + Clone + PartialOrd,
+    D: Clone + PartialOrd + Default,
+    C: ClockSource<I, D>,
+    S: StateMachine,
+{
+    /// Creates a new [QueuedButton] pushing the events into `producer`.
+    pub fn new(button: Button<P, I, D, C, K, S>, producer: EventProducer<'a, D, N>) -> Self {
+        Self {
+            button,
+            producer,
+            dropped: 0,
+        }
+    }
+
+    /// Updates button state, see [Button::tick], and pushes the produced event, if any, into the queue.
+    /// Returns [false] if the event was dropped because the queue is full.
+    pub fn tick(&mut self) -> bool {
+        let Some(event) = self.button.update() else {
+            return true;
+        };
+
+        if self.producer.enqueue(event).is_err() {
+            self.dropped = self.dropped.saturating_add(1);
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Returns the number of events dropped because the queue was full, saturating at [usize::MAX].
+    pub const fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Returns the inner button and producer.
+    pub fn into_inner(self) -> (Button<P, I, D, C, K, S>, EventProducer<'a, D, N>) {
+        (self.button, self.producer)
+    }
+}
+
+impl<B: fmt::Debug, D, const N: usize> fmt::Debug for QueuedButton<'_, B, D, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueuedButton")
+            .field("button", &self.button)
+            .field("dropped", &self.dropped)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B, D, const N: usize> Deref for QueuedButton<'_, B, D, N> {
+    type Target = B;
+
+    fn deref(&self) -> &B {
+        &self.button
+    }
+}
+
+impl<B, D, const N: usize> DerefMut for QueuedButton<'_, B, D, N> {
+    fn deref_mut(&mut self) -> &mut B {
+        &mut self.button
+    }
+}
@@ -0,0 +1,20 @@
+use core::time::Duration;
+
+use crate::{ButtonConfig, StateKind};
+
+/// The persistent part of a [Button](crate::Button), e.g. to keep it across deep sleep,
+/// see [Button::snapshot](crate::Button::snapshot) and [Button::from_snapshot](crate::Button::from_snapshot).
+///
+/// Instants are not saved, since they are meaningless after a restart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot<D = Duration> {
+    /// See [Button::raw_clicks](crate::Button::raw_clicks).
+    pub clicks: usize,
+    /// See [Button::raw_holds](crate::Button::raw_holds).
+    pub holds: usize,
+    /// See [Button::state_kind](crate::Button::state_kind).
+    pub state: StateKind,
+    /// See [Button::config](crate::Button::config).
+    pub config: ButtonConfig<D>,
+}
@@ -0,0 +1,158 @@
+use core::{ops::Sub, time::Duration};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::sleep,
+};
+
+use crate::{Button, ButtonConfig, ButtonEvent, ClockSource, Mode, PinWrapper};
+
+/// A scripted pin level at the given instant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sample<I> {
+    /// The button is pressed.
+    Down(I),
+    /// The button is released.
+    Up(I),
+}
+
+/// A pin holding the level set by the [Simulator].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SimPin(pub bool);
+
+impl PinWrapper for SimPin {
+    fn is_high(&mut self) -> bool {
+        self.0
+    }
+}
+
+/// A pin pressed and released by a test, shared between its clones,
+/// e.g. with a [Button] ticked in another thread.
+///
+/// Its level follows the given [Mode] like a real pin: low while pressed in [PullUp](Mode::PullUp),
+/// high while pressed in [PullDown](Mode::PullDown), so a button configured with another mode misreads it.
+#[derive(Debug, Clone, Default)]
+pub struct MockPin {
+    pressed: Arc<AtomicBool>,
+    mode: Mode,
+}
+
+impl MockPin {
+    /// Creates a released pin wired for the given [Mode].
+    pub fn new(mode: Mode) -> Self {
+        Self {
+            pressed: Arc::default(),
+            mode,
+        }
+    }
+
+    /// Presses the pin without waiting.
+    pub fn press(&self) {
+        self.pressed.store(true, Ordering::SeqCst);
+    }
+
+    /// Releases the pin without waiting.
+    pub fn release(&self) {
+        self.pressed.store(false, Ordering::SeqCst);
+    }
+
+    /// Returns [true] if the pin is pressed.
+    pub fn is_pressed(&self) -> bool {
+        self.pressed.load(Ordering::SeqCst)
+    }
+
+    /// Clicks the pin, keeping each level for the `debounce` time of the config.
+    pub fn click(&self, config: &ButtonConfig) {
+        self.press();
+        sleep(config.debounce);
+        self.release();
+        sleep(config.debounce);
+    }
+
+    /// Holds the pin for the `hold` time of the config and releases it, keeping each level debounced.
+    pub fn hold(&self, config: &ButtonConfig) {
+        self.press();
+        sleep(config.debounce + config.hold);
+        self.release();
+        sleep(config.debounce);
+    }
+}
+
+impl PinWrapper for MockPin {
+    fn is_high(&mut self) -> bool {
+        self.is_pressed() == self.mode.is_pulldown()
+    }
+}
+
+/// A clock holding the instant set by the [Simulator].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SimClock<I>(pub I);
+
+impl<I, D> ClockSource<I, D> for SimClock<I>
+where
+    I: Sub<I, Output = D> + Clone,
+{
+    fn now(&self) -> I {
+        self.0.clone()
+    }
+}
+
+/// Drives a [Button] through a script of [Sample]s without sleeping, for concise gesture tests.
+///
+/// ```
+/// use std::time::Duration;
+/// use button_driver::{testing::{Sample, Simulator}, ButtonConfig, ButtonEvent};
+///
+/// let ms = Duration::from_millis;
+/// let mut sim = Simulator::new(ButtonConfig::default(), Duration::ZERO);
+/// let events = sim.run(&[
+///     Sample::Down(ms(10)),
+///     Sample::Down(ms(20)),
+///     Sample::Up(ms(50)),
+///     Sample::Up(ms(300)),
+/// ]);
+///
+/// assert_eq!(events[0], ButtonEvent::Press);
+/// assert!(sim.button.is_clicked());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Simulator<I, D = Duration> {
+    /// The simulated button, inspect it after [run](Self::run).
+    pub button: Button<SimPin, I, D, SimClock<I>>,
+}
+
+impl<I, D> Simulator<I, D>
+where
+    I: Sub<I, Output = D> + Clone + PartialOrd,
+    D: Clone + PartialOrd + Default,
+{
+    /// Creates a new [Simulator] with the released button at the `start` instant.
+    pub fn new(config: ButtonConfig<D>, start: I) -> Self {
+        let pin = SimPin(config.mode == Mode::PullUp);
+        let mut button = Button::with_clock(pin, config, SimClock(start));
+        button.tick();
+        Self { button }
+    }
+
+    /// Ticks the button once per sample, returns the produced events.
+    ///
+    /// Samples must be in chronological order. Repeat the last sample at a later instant
+    /// to let the timeouts pass, e.g. `Down(t + debounce)` to confirm a press
+    /// or `Up(t + release)` to finish the gesture.
+    pub fn run(&mut self, script: &[Sample<I>]) -> Vec<ButtonEvent<D>> {
+        script
+            .iter()
+            .filter_map(|sample| {
+                let (now, pressed) = match sample {
+                    Sample::Down(now) => (now, true),
+                    Sample::Up(now) => (now, false),
+                };
+                self.button.clock.0 = now.clone();
+                self.button.pin.0 = pressed == (self.button.config.mode == Mode::PullDown);
+                self.button.update()
+            })
+            .collect()
+    }
+}
@@ -6,6 +6,7 @@ use std::{
 
 use parking_lot::Mutex;
 
+use super::instant::tests::*;
 use super::pin_wrapper::tests::*;
 use super::*;
 
@@ -246,3 +247,2372 @@ fn test_thread_clicks_holds() {
         button.reset()
     }
 }
+
+#[test]
+fn test_read_raw_debounced() {
+    let pin = MockPin::default();
+    let mut button = Button::<_, Instant>::new(pin, CONFIG);
+    button.tick();
+
+    button.pin.press();
+    assert!(button.read_raw());
+    assert!(!button.read_debounced());
+    assert_eq!(button.state, State::Released);
+
+    button.tick();
+    assert!(!button.read_debounced());
+    sleep(CONFIG.debounce);
+    button.tick();
+    assert!(button.read_debounced());
+
+    button.pin.release();
+    assert!(!button.read_raw());
+    assert!(button.read_debounced());
+}
+
+#[test]
+fn test_pause_resume() {
+    let pin = MockPin::default();
+    let mut button = Button::<_, Instant>::new(pin, CONFIG);
+    button.tick();
+
+    button.pause();
+    assert!(button.is_paused());
+
+    button.pin.press();
+    button.tick();
+    sleep(CONFIG.debounce);
+    button.tick();
+    assert_eq!(button.state, State::Released);
+    assert_eq!(button.raw_clicks(), 0);
+
+    button.resume();
+    assert!(!button.is_paused());
+    assert_eq!(button.state, State::Unknown);
+
+    button.release_button();
+    assert_eq!(button.state, State::Released);
+    assert_eq!(button.clicks(), 0);
+}
+
+#[test]
+fn test_resume_discards_timing() {
+    let config = ButtonConfig {
+        min_click_interval: Some(Duration::from_millis(5)),
+        ..CONFIG
+    };
+    let mut button = Button::with_clock(MockPin::default(), config, MockClock::default());
+    button.tick();
+
+    button.click_for(CONFIG.debounce);
+    button.advance(Duration::from_millis(10));
+    button.click_for(CONFIG.hold);
+    assert!(button.inter_click_time().is_some());
+    assert!(button.peek_event().is_some());
+
+    button.pause();
+    button.resume();
+    assert_eq!(button.inter_click_time(), None);
+    assert!(!button.hold_completed());
+    assert_eq!(button.peek_event(), None);
+    assert_eq!(button.repeats(), 0);
+
+    // A click right before the pause does not make the next press too soon
+    button.tick();
+    button.click_for(CONFIG.debounce);
+    button.pause();
+    button.resume();
+    button.tick();
+    button.click_for(CONFIG.debounce);
+    assert_eq!(button.raw_clicks(), 1);
+}
+
+#[test]
+fn test_total_presses() {
+    let pin = MockPin::default();
+    let mut button = Button::<_, Instant>::new(pin, CONFIG);
+    button.tick();
+
+    button.press_button();
+    button.release_button();
+    button.press_button();
+    button.release_button();
+    sleep(CONFIG.release);
+    button.tick();
+    button.reset();
+    assert_eq!(button.total_presses(), 2);
+
+    button.total_presses = u32::MAX;
+    button.press_button();
+    assert_eq!(button.total_presses(), u32::MAX);
+
+    button.reset_stats();
+    assert_eq!(button.total_presses(), 0);
+}
+
+#[test]
+fn test_release_debounce() {
+    let config = ButtonConfig {
+        release_debounce: Some(Duration::from_millis(2)),
+        ..CONFIG
+    };
+    let pin = MockPin::default();
+    let mut button = Button::<_, Instant>::new(pin, config);
+    button.tick();
+
+    button.press_button();
+    assert!(matches!(button.state, State::Pressed(_)));
+
+    // bouncing contacts on release
+    for _ in 0..5 {
+        button.release_button();
+        assert!(matches!(button.state, State::Pressed(_)));
+        button.pin.press();
+        button.tick();
+        assert!(matches!(button.state, State::Pressed(_)));
+    }
+
+    button.release_button();
+    sleep(Duration::from_millis(2));
+    button.tick();
+    assert!(matches!(button.state, State::Up(_)));
+
+    sleep(CONFIG.release);
+    button.tick();
+    assert_eq!(button.clicks(), 1);
+
+    button.reset();
+    button.press_button();
+    sleep(CONFIG.hold);
+    button.tick();
+    assert!(matches!(button.state, State::Held(_)));
+
+    // bouncing contacts on hold release
+    for _ in 0..5 {
+        button.release_button();
+        assert!(matches!(button.state, State::Held(_)));
+        button.pin.press();
+        button.tick();
+        assert!(matches!(button.state, State::Held(_)));
+    }
+
+    button.release_button();
+    sleep(Duration::from_millis(2));
+    button.tick();
+    assert!(matches!(button.state, State::Up(_)));
+
+    sleep(CONFIG.release);
+    button.tick();
+    assert_eq!(button.clicks(), 0);
+    assert_eq!(button.holds(), 1);
+}
+
+#[test]
+fn test_set_state() {
+    let pin = MockPin::default();
+    let mut button = Button::<_, Instant>::new(pin, CONFIG);
+    button.set_state(State::Released);
+    assert_eq!(button.state, State::Released);
+
+    button.set_state(State::Pressed(Instant::now()));
+    button.release_button();
+    assert!(matches!(button.state, State::Up(_)));
+
+    sleep(CONFIG.release);
+    button.tick();
+    assert_eq!(button.state, State::Released);
+    assert_eq!(button.clicks(), 0);
+}
+
+#[test]
+fn test_clock_source() {
+    let clock1 = MockClock::default();
+    let clock2 = MockClock::default();
+    let mut button1 = Button::with_clock(MockPin::default(), CONFIG, clock1.clone());
+    let mut button2 = Button::with_clock(MockPin::default(), CONFIG, clock2.clone());
+
+    for button in [&mut button1, &mut button2] {
+        button.tick();
+        button.pin.press();
+        button.tick();
+        assert!(matches!(button.state, State::Down(_)));
+    }
+
+    clock1.advance(CONFIG.debounce);
+    button1.tick();
+    button2.tick();
+    assert!(matches!(button1.state, State::Pressed(_)));
+    assert!(matches!(button2.state, State::Down(_)));
+
+    clock2.advance(CONFIG.hold);
+    button2.tick();
+    button2.tick();
+    assert!(matches!(button2.state, State::Held(_)));
+    assert_eq!(button2.current_holding_time(), Some(CONFIG.hold));
+}
+
+#[test]
+fn test_min_press() {
+    let config = ButtonConfig {
+        min_press: Some(Duration::from_millis(10)),
+        ..CONFIG
+    };
+    let clock = MockClock::default();
+    let mut button = Button::with_clock(MockPin::default(), config, clock.clone());
+    let mut events = Vec::new();
+    let mut tick = |button: &mut Button<_, _, _, _>| events.extend(button.update());
+    tick(&mut button);
+
+    // 1ms blip
+    button.pin.press();
+    tick(&mut button);
+    clock.advance(CONFIG.debounce);
+    tick(&mut button);
+    assert!(matches!(button.state, State::Down(_)));
+    clock.advance(Duration::from_millis(1) - CONFIG.debounce);
+    button.pin.release();
+    tick(&mut button);
+    assert_eq!(button.state, State::Released);
+
+    clock.advance(CONFIG.release);
+    tick(&mut button);
+    assert_eq!(button.clicks(), 0);
+    assert_eq!(button.total_presses(), 0);
+
+    // a real click followed by a blip
+    button.pin.press();
+    tick(&mut button);
+    clock.advance(Duration::from_millis(10));
+    tick(&mut button);
+    button.pin.release();
+    tick(&mut button);
+    button.pin.press();
+    tick(&mut button);
+    clock.advance(Duration::from_millis(1));
+    button.pin.release();
+    tick(&mut button);
+    assert!(button.state.is_up());
+
+    clock.advance(CONFIG.release);
+    tick(&mut button);
+    assert_eq!(button.clicks(), 1);
+    assert_eq!(button.total_presses(), 1);
+    assert_eq!(
+        events,
+        [
+            ButtonEvent::Press,
+            ButtonEvent::Release,
+            ButtonEvent::Settled {
+                clicks: 1,
+                holds: 0,
+                held: None
+            }
+        ]
+    );
+}
+
+#[test]
+fn test_effective_hold_time() {
+    let clock = MockClock::default();
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, clock.clone());
+    button.tick();
+    assert_eq!(button.effective_hold_time(), None);
+
+    button.pin.press();
+    button.tick();
+    clock.advance(CONFIG.debounce);
+    button.tick();
+    clock.advance(CONFIG.hold);
+    button.tick();
+    let held = CONFIG.debounce + CONFIG.hold;
+    assert_eq!(button.effective_hold_time(), Some(held));
+
+    clock.advance(CONFIG.debounce);
+    let held = held + CONFIG.debounce;
+    assert_eq!(button.effective_hold_time(), Some(held));
+
+    button.pin.release();
+    button.tick();
+    clock.advance(CONFIG.release);
+    assert_eq!(button.effective_hold_time(), Some(held));
+    button.tick();
+    assert_eq!(button.effective_hold_time(), button.held_time());
+
+    button.reset();
+    assert_eq!(button.effective_hold_time(), None);
+}
+
+#[test]
+fn test_tick_and_reset() {
+    let clock = MockClock::default();
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, clock.clone());
+    assert_eq!(button.tick_and_reset(), None);
+
+    button.pin.press();
+    assert_eq!(button.tick_and_reset(), None);
+    clock.advance(CONFIG.debounce);
+    assert_eq!(button.tick_and_reset(), Some(ButtonEvent::Press));
+
+    button.pin.release();
+    assert_eq!(button.tick_and_reset(), Some(ButtonEvent::Release));
+    clock.advance(CONFIG.release);
+    assert_eq!(
+        button.tick_and_reset(),
+        Some(ButtonEvent::Settled {
+            clicks: 1,
+            holds: 0,
+            held: None
+        })
+    );
+    assert_eq!(button.clicks(), 0);
+
+    button.pin.press();
+    assert_eq!(button.tick_and_reset(), None);
+    clock.advance(CONFIG.hold);
+    assert_eq!(button.tick_and_reset(), Some(ButtonEvent::Press));
+    assert_eq!(button.tick_and_reset(), Some(ButtonEvent::HoldStart));
+    button.pin.release();
+    assert_eq!(
+        button.tick_and_reset(),
+        Some(ButtonEvent::HoldEnd(CONFIG.hold))
+    );
+    clock.advance(CONFIG.release);
+    assert_eq!(
+        button.tick_and_reset(),
+        Some(ButtonEvent::Settled {
+            clicks: 0,
+            holds: 1,
+            held: Some(CONFIG.hold)
+        })
+    );
+    assert_eq!(button.holds(), 0);
+}
+
+#[test]
+fn test_bank_buttons() {
+    #[derive(Default)]
+    struct MockPort {
+        value: u32,
+        reads: usize,
+    }
+
+    impl PortWrapper for MockPort {
+        fn read(&mut self) -> u32 {
+            self.reads += 1;
+            self.value
+        }
+    }
+
+    let clock = MockClock::default();
+    let mut bank: BankButtons<_, _, 16, _, _> =
+        BankButtons::with_clock(MockPort::default(), CONFIG, clock.clone());
+    bank.tick();
+    assert_eq!(bank.port.reads, 1);
+
+    bank.port.value = 0b1000_0000_0000_0010;
+    bank.tick();
+    clock.advance(CONFIG.debounce);
+    bank.tick();
+    bank.port.value = 0;
+    bank.tick();
+    clock.advance(CONFIG.release);
+    bank.tick();
+    assert_eq!(bank.port.reads, 5);
+
+    for (bit, button) in bank.buttons.iter().enumerate() {
+        let clicks = if bit == 1 || bit == 15 { 1 } else { 0 };
+        assert_eq!(button.clicks(), clicks);
+    }
+}
+
+#[test]
+fn test_is_settled() {
+    let clock = MockClock::default();
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, clock.clone());
+    button.tick();
+    assert!(!button.is_settled());
+
+    button.pin.press();
+    button.tick();
+    clock.advance(CONFIG.debounce);
+    button.tick();
+    button.pin.release();
+    button.tick();
+    assert!(!button.is_settled());
+
+    clock.advance(CONFIG.release);
+    button.tick();
+    assert!(button.is_settled());
+    assert_eq!(button.clicks(), 1);
+
+    button.tick();
+    assert!(!button.is_settled());
+    assert_eq!(button.clicks(), 1);
+}
+
+#[test]
+fn test_clicks_before_hold() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+
+    button.click_for(CONFIG.debounce);
+    button.click_for(CONFIG.debounce);
+    button.click_for(CONFIG.hold);
+    button.click_for(CONFIG.debounce);
+    assert_eq!(button.clicks_before_hold(), 0);
+
+    button.advance(CONFIG.release);
+    assert_eq!(button.clicks(), 3);
+    assert_eq!(button.holds(), 1);
+    assert_eq!(button.clicks_before_hold(), 2);
+
+    button.reset();
+    assert_eq!(button.clicks_before_hold(), 0);
+}
+
+#[test]
+fn test_threshold_pin() {
+    let sample = std::cell::Cell::new(0);
+    let mut pin = ThresholdPin::with_hysteresis(|| sample.get(), 400, 600);
+    assert!(!pin.is_high());
+
+    for (value, level) in [
+        (500, false),
+        (600, true),
+        (500, true),
+        (399, false),
+        (500, false),
+    ] {
+        sample.set(value);
+        assert_eq!(pin.is_high(), level, "sample {value}");
+    }
+
+    let mut pin = ThresholdPin::new(|| sample.get(), 500);
+    assert!(pin.is_high());
+    sample.set(499);
+    assert!(!pin.is_high());
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn test_history() {
+    let mut button =
+        Button::with_clock(MockPin::default(), CONFIG, MockClock::default()).with_history::<3>();
+    button.tick();
+    assert!(button.history().is_empty());
+
+    button.click_for(CONFIG.debounce);
+    assert_eq!(
+        button.history().as_slice(),
+        [ButtonEvent::Press, ButtonEvent::Release]
+    );
+
+    button.advance(CONFIG.release);
+    button.pin.press_raw();
+    button.tick();
+    button.advance(CONFIG.debounce);
+    assert_eq!(
+        button.history().as_slice(),
+        [
+            ButtonEvent::Release,
+            ButtonEvent::Settled {
+                clicks: 1,
+                holds: 0,
+                held: None
+            },
+            ButtonEvent::Press
+        ]
+    );
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn test_producer() {
+    let mut queue = heapless::spsc::Queue::<_, 3>::new();
+    let (producer, mut consumer) = queue.split();
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default())
+        .with_producer(producer);
+    assert!(button.tick());
+    assert_eq!(consumer.dequeue(), None);
+
+    button.pin.press_raw();
+    assert!(button.tick());
+    button.clock.advance(CONFIG.debounce);
+    assert!(button.tick());
+    button.pin.release_raw();
+    assert!(button.tick());
+    assert_eq!(consumer.dequeue(), Some(ButtonEvent::Press));
+
+    button.clock.advance(CONFIG.release);
+    assert!(button.tick());
+    button.pin.press_raw();
+    assert!(button.tick());
+    button.clock.advance(CONFIG.debounce);
+    assert!(!button.tick());
+    assert_eq!(button.dropped(), 1);
+
+    assert_eq!(consumer.dequeue(), Some(ButtonEvent::Release));
+    assert_eq!(
+        consumer.dequeue(),
+        Some(ButtonEvent::Settled {
+            clicks: 1,
+            holds: 0,
+            held: None
+        })
+    );
+    assert_eq!(consumer.dequeue(), None);
+}
+
+#[test]
+fn test_replay() {
+    let ms = Duration::from_millis;
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+
+    #[rustfmt::skip]
+    let cases = [
+        (vec![(ms(0), false), (ms(1), true), (ms(2), true), (ms(5), false), (ms(40), false)],
+         vec![ButtonEvent::Press, ButtonEvent::Release, ButtonEvent::Settled { clicks: 1, holds: 0, held: None }]),
+        (vec![(ms(50), true), (ms(51), true), (ms(55), false), (ms(60), true), (ms(61), true), (ms(65), false), (ms(100), false)],
+         vec![ButtonEvent::Press, ButtonEvent::Release, ButtonEvent::Press, ButtonEvent::Release, ButtonEvent::Settled { clicks: 2, holds: 0, held: None }]),
+        (vec![(ms(200), true), (ms(201), true), (ms(700), true), (ms(800), false), (ms(900), false)],
+         vec![ButtonEvent::Press, ButtonEvent::HoldStart, ButtonEvent::HoldEnd(ms(600)), ButtonEvent::Settled { clicks: 0, holds: 1, held: Some(ms(600)) }]),
+    ];
+
+    for (samples, events) in cases {
+        assert_eq!(button.replay(samples).collect::<Vec<_>>(), events);
+        button.reset();
+    }
+}
+
+#[test]
+fn test_on_edge() {
+    let clock = MockClock::default();
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, clock.clone());
+    let mut latch = EdgeLatch::new();
+    button.process(latch.take());
+    assert_eq!(button.state, State::Unknown);
+
+    latch.on_edge(true, clock.now());
+    assert_eq!(button.state, State::Unknown);
+    button.process(latch.take());
+    assert!(matches!(button.state, State::Down(_)));
+
+    clock.advance(CONFIG.debounce);
+    button.process(latch.take());
+    assert!(matches!(button.state, State::Pressed(_)));
+
+    clock.advance(CONFIG.debounce);
+    latch.on_edge(false, clock.now());
+    clock.advance(CONFIG.release);
+    button.process(latch.take());
+    assert_eq!(button.state, State::Released);
+    assert_eq!(button.clicks(), 1);
+    assert_eq!(latch.level(), Some(false));
+}
+
+#[test]
+fn test_max_clicks() {
+    let config = ButtonConfig {
+        max_clicks: Some(2),
+        ..CONFIG
+    };
+    let mut button = Button::with_clock(MockPin::default(), config, MockClock::default());
+    button.tick();
+
+    for _ in 0..4 {
+        button.click_for(CONFIG.debounce);
+    }
+    assert_eq!(button.raw_clicks(), 2);
+    assert!(button.state.is_up());
+
+    button.click_for(CONFIG.hold);
+    assert_eq!(button.raw_clicks(), 2);
+    assert_eq!(button.raw_holds(), 1);
+
+    button.advance(CONFIG.release);
+    assert_eq!(button.clicks(), 2);
+    assert_eq!(button.holds(), 1);
+}
+
+#[test]
+fn test_fn_pin() {
+    let level = std::cell::Cell::new(false);
+    let mut button = Button::with_clock(FnPin(|| level.get()), CONFIG, MockClock::default());
+    button.tick();
+    assert_eq!(button.state, State::Released);
+
+    level.set(true);
+    button.tick();
+    assert!(button.state.is_down());
+}
+
+#[test]
+fn test_longer_than() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+    let second = Duration::from_secs(1);
+
+    button.pin.press_raw();
+    button.tick();
+    button.advance(CONFIG.debounce);
+    button.advance(second);
+    assert!(button.holding_longer_than(&CONFIG.hold));
+    assert!(!button.holding_longer_than(&(second * 2)));
+    assert!(!button.held_longer_than(&CONFIG.hold));
+
+    button.pin.release_raw();
+    button.tick();
+    button.advance(CONFIG.release);
+    assert!(!button.holding_longer_than(&CONFIG.hold));
+    assert!(button.held_longer_than(&second));
+    assert!(!button.held_longer_than(&(second * 2)));
+}
+
+#[test]
+fn test_click_hold_policy() {
+    for (policy, clicks) in [
+        (ClickHoldPolicy::HoldPreservesClicks, 2),
+        (ClickHoldPolicy::HoldCancelsClicks, 0),
+    ] {
+        let config = ButtonConfig {
+            click_hold_policy: policy,
+            ..CONFIG
+        };
+        let mut button = Button::with_clock(MockPin::default(), config, MockClock::default());
+        button.tick();
+
+        button.click_for(CONFIG.debounce);
+        button.click_for(CONFIG.debounce);
+        button.click_for(CONFIG.hold);
+        button.advance(CONFIG.release);
+
+        assert_eq!(button.clicks(), clicks, "{policy:?}");
+        assert_eq!(button.holds(), 1, "{policy:?}");
+    }
+}
+
+#[test]
+fn test_auto_reset() {
+    let config = ButtonConfig {
+        auto_reset: true,
+        ..CONFIG
+    };
+    let mut button = Button::with_clock(MockPin::default(), config, MockClock::default());
+    button.tick();
+
+    button.click_for(CONFIG.debounce);
+    button.advance(CONFIG.release);
+    assert!(button.is_clicked());
+
+    button.tick();
+    assert!(!button.is_clicked());
+    assert_eq!(button.raw_clicks(), 0);
+}
+
+#[test]
+fn test_system_instant() {
+    let now = SystemInstant::now();
+    let before = SystemInstant(now.0 - Duration::from_secs(1));
+
+    assert_eq!(now - before, Duration::from_secs(1));
+    // a backward clock jump
+    assert_eq!(before - now, Duration::ZERO);
+    assert!(before.elapsed() >= Duration::from_secs(1));
+}
+
+#[test]
+fn test_backward_clock() {
+    #[derive(Debug, Clone, PartialEq, PartialOrd)]
+    struct Ticks(u64);
+
+    impl core::ops::Sub for Ticks {
+        type Output = u64;
+
+        fn sub(self, rhs: Self) -> u64 {
+            // panics on underflow in debug builds
+            self.0 - rhs.0
+        }
+    }
+
+    struct TicksClock;
+
+    impl ClockSource<Ticks, u64> for TicksClock {
+        fn now(&self) -> Ticks {
+            Ticks(0)
+        }
+    }
+
+    let config = ButtonConfig::new(1, 30, 500, Mode::PullDown);
+    let mut button = Button::with_clock(MockPin::default(), config, TicksClock);
+    let samples = [(Ticks(10), true), (Ticks(5), true), (Ticks(12), true)];
+    assert_eq!(
+        button.replay(samples).collect::<Vec<_>>(),
+        [ButtonEvent::Press]
+    );
+
+    let future = SystemInstant(SystemInstant::now().0 + Duration::from_secs(1));
+    assert_eq!(future.checked_elapsed(), None);
+    assert!(SystemInstant::now().checked_elapsed().is_some());
+}
+
+#[test]
+fn test_matches() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+
+    button.click_for(CONFIG.debounce);
+    button.click_for(CONFIG.debounce);
+    button.advance(CONFIG.release);
+    assert!(button.matches(Gesture::Click(2)));
+    assert!(!button.matches(Gesture::Click(1)));
+    assert!(!button.matches(Gesture::Hold));
+    button.reset();
+
+    button.click_for(CONFIG.hold);
+    button.advance(CONFIG.release);
+    assert!(button.matches(Gesture::Hold));
+    assert!(button.matches(Gesture::ClicksThenHold(0)));
+    assert!(!button.matches(Gesture::Click(0)));
+    button.reset();
+
+    button.click_for(CONFIG.debounce);
+    button.click_for(CONFIG.hold);
+    assert!(!button.matches(Gesture::ClicksThenHold(1)));
+    button.advance(CONFIG.release);
+    assert!(button.matches(Gesture::ClicksThenHold(1)));
+    assert!(!button.matches(Gesture::Hold));
+}
+
+#[test]
+fn test_transitions() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    let mut transitions = Vec::new();
+    let mut tick = |button: &mut Button<_, _, _, _>| {
+        button.tick_with(|old, new| transitions.push((*old, *new)));
+    };
+
+    tick(&mut button);
+    button.pin.press_raw();
+    tick(&mut button);
+    tick(&mut button);
+    button.clock.advance(CONFIG.debounce);
+    tick(&mut button);
+
+    assert_eq!(transitions.len(), 3);
+    assert!(transitions[0].0.is_unknown() && transitions[0].1.is_released());
+    assert!(transitions[1].0.is_released() && transitions[1].1.is_down());
+    assert!(transitions[2].0.is_down() && transitions[2].1.is_pressed());
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    button.on_transition(Some(|_, new| {
+        assert!(new.is_up());
+        CALLS.fetch_add(1, Ordering::Relaxed);
+    }));
+    button.pin.release_raw();
+    button.tick();
+    button.tick();
+    assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<Button<MockPin, Instant>>();
+    assert_send_sync::<Button<MockPin, Duration, Duration, MockClock>>();
+    assert_send_sync::<BankButtons<fn() -> u32, Instant, 4>>();
+}
+
+#[test]
+fn test_tick_sampled() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+
+    let ms = Duration::from_millis;
+    // Two fast clicks within one slow poll period.
+    button.tick_sampled(&[
+        (ms(0), false),
+        (ms(5), true),
+        (ms(10), true),
+        (ms(15), false),
+        (ms(20), true),
+        (ms(25), true),
+        (ms(30), false),
+    ]);
+    assert_eq!(button.raw_clicks(), 2);
+
+    button.tick_sampled(&[(ms(30) + CONFIG.release, false)]);
+    assert!(button.is_double_clicked());
+}
+
+#[test]
+fn test_config_mut() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+    assert_eq!(button.config(), &CONFIG);
+
+    button.config_mut().hold = CONFIG.hold * 2;
+    button.click_for(CONFIG.hold);
+    button.advance(CONFIG.release);
+    assert!(button.is_clicked());
+
+    button.config_mut().mode = Mode::PullDown;
+    button.tick();
+    assert!(button.is_clicked());
+
+    button.config_mut().mode = Mode::PullUp;
+    assert!(button.is_clicked());
+    // A high level is released in the pull up mode.
+    button.pin.press_raw();
+    button.tick();
+    assert_eq!(button.state, State::Released);
+    assert_eq!(button.raw_clicks(), 0);
+    assert_eq!(button.config().mode, Mode::PullUp);
+
+    let sibling = Button::<_, Instant>::new(MockPin::default(), button.config_cloned());
+    assert_eq!(sibling.config(), button.config());
+}
+
+#[test]
+fn test_repeat_accel() {
+    let ms = Duration::from_millis;
+    let config = ButtonConfig {
+        repeat: Some(ms(100)),
+        repeat_accel: Some((2, ms(20))),
+        ..CONFIG
+    };
+    let mut button = Button::with_clock(MockPin::default(), config, MockClock::default());
+    button.tick();
+    assert_eq!(button.repeat_interval_now(), None);
+
+    button.pin.press_raw();
+    button.tick();
+    button.advance(CONFIG.hold);
+    button.tick();
+    assert!(button.state.is_held());
+    assert_eq!(button.repeat_interval_now(), Some(ms(100)));
+
+    button.advance(ms(50));
+    assert_eq!(button.repeats(), 0);
+    button.advance(ms(50));
+    button.advance(ms(100));
+    assert_eq!(button.repeats(), 2);
+    assert_eq!(button.repeat_interval_now(), Some(ms(20)));
+
+    button.advance(ms(20));
+    button.advance(ms(20));
+    assert_eq!(button.repeats(), 4);
+
+    button.pin.release_raw();
+    button.tick();
+    assert_eq!(button.repeat_interval_now(), None);
+    assert_eq!(button.repeats(), 4);
+}
+
+#[test]
+fn test_first_press_confirmed() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+
+    button.pin.press_raw();
+    button.tick();
+    assert!(!button.first_press_confirmed());
+    button.advance(CONFIG.debounce);
+    assert!(button.first_press_confirmed());
+    button.tick();
+    assert!(!button.first_press_confirmed());
+
+    button.pin.release_raw();
+    button.tick();
+    button.pin.press_raw();
+    button.tick();
+    button.advance(CONFIG.debounce);
+    assert!(!button.first_press_confirmed());
+}
+
+#[test]
+fn test_tick_count() {
+    let pressed = std::cell::Cell::new(false);
+    let config = ButtonConfig {
+        mode: Mode::PullDown,
+        ..ButtonConfig::<u32>::default()
+    };
+    let mut button = Button::<_, TickCount, u32>::new(FnPin(|| pressed.get()), config);
+    let run = |button: &mut Button<_, _, _>, ticks| {
+        for _ in 0..ticks {
+            TickCount::increment();
+            button.tick();
+        }
+    };
+
+    run(&mut button, 1);
+    pressed.set(true);
+    run(&mut button, 5);
+    pressed.set(false);
+    run(&mut button, config.release + 1);
+    assert!(button.is_clicked());
+
+    pressed.set(true);
+    run(&mut button, config.hold + 1);
+    pressed.set(false);
+    run(&mut button, config.release + 1);
+    assert_eq!(button.held_time(), Some(config.hold + 1));
+}
+
+#[test]
+fn test_tick_count_wrap() {
+    use std::cell::Cell;
+
+    let ticks = Cell::new(u32::MAX - 10);
+    let pressed = Cell::new(false);
+    let config = ButtonConfig::new(2, 10, 50, Mode::PullDown);
+    let mut button = Button::with_clock(FnPin(|| pressed.get()), config, TickClock(&ticks));
+    let run = |button: &mut Button<_, TickCount, u32, _>, level, n| {
+        pressed.set(level);
+        for _ in 0..n {
+            ticks.set(ticks.get().wrapping_add(1));
+            button.tick();
+        }
+    };
+
+    run(&mut button, false, 1);
+    run(&mut button, true, 5);
+    run(&mut button, false, 11);
+    assert!(ticks.get() < 10);
+    assert!(button.is_clicked());
+
+    run(&mut button, true, 60);
+    run(&mut button, false, 11);
+    assert_eq!(button.held_time(), Some(60));
+}
+
+#[cfg(feature = "embedded_hal")]
+#[test]
+fn test_with_led() {
+    use embedded_hal::digital::{ErrorType, OutputPin, PinState};
+
+    #[derive(Debug, Default)]
+    struct Led(bool);
+
+    impl ErrorType for Led {
+        type Error = core::convert::Infallible;
+    }
+
+    impl OutputPin for Led {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0 = false;
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0 = true;
+            Ok(())
+        }
+    }
+
+    let button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    let mut button = button.with_led(Led(true), PinState::Low);
+    button.tick();
+    assert!(button.led.0);
+
+    button.pin.press_raw();
+    button.tick();
+    assert!(button.led.0);
+    button.clock.advance(CONFIG.debounce);
+    button.tick();
+    assert!(!button.led.0);
+
+    button.pin.release_raw();
+    button.tick();
+    assert!(button.led.0);
+    assert_eq!(button.raw_clicks(), 1);
+}
+
+#[test]
+fn test_inter_click_time() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+
+    button.click_for(CONFIG.debounce);
+    assert_eq!(button.inter_click_time(), None);
+
+    button.advance(Duration::from_millis(10));
+    button.click_for(CONFIG.debounce);
+    assert_eq!(
+        button.inter_click_time(),
+        Some(CONFIG.debounce + Duration::from_millis(10))
+    );
+
+    button.advance(CONFIG.release);
+    assert!(button.is_double_clicked());
+    assert!(button.inter_click_time().is_some());
+
+    button.click_for(CONFIG.debounce);
+    assert_eq!(button.inter_click_time(), None);
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_simulator() {
+    use testing::{Sample::*, Simulator};
+
+    let ms = Duration::from_millis;
+    let mut sim = Simulator::new(CONFIG, Duration::ZERO);
+    let events = sim.run(&[
+        Down(ms(10)),
+        Down(ms(11)),
+        Up(ms(20)),
+        Down(ms(30)),
+        Down(ms(31)),
+        Down(ms(600)),
+        Up(ms(700)),
+        Up(ms(800)),
+    ]);
+
+    assert_eq!(
+        events,
+        [
+            ButtonEvent::Press,
+            ButtonEvent::Release,
+            ButtonEvent::Press,
+            ButtonEvent::HoldStart,
+            ButtonEvent::HoldEnd(ms(670)),
+            ButtonEvent::Settled {
+                clicks: 1,
+                holds: 1,
+                held: Some(ms(670)),
+            },
+        ]
+    );
+    assert!(sim.button.matches(Gesture::ClicksThenHold(1)));
+}
+
+#[test]
+fn test_click_saturation() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+
+    button.clicks = usize::MAX - 1;
+    button.click_for(CONFIG.debounce);
+    button.click_for(CONFIG.debounce);
+    button.click_for(CONFIG.debounce);
+    assert_eq!(button.raw_clicks(), usize::MAX);
+
+    button.click_for(CONFIG.hold);
+    assert_eq!(button.raw_clicks(), usize::MAX);
+    button.holds = usize::MAX;
+    button.click_for(CONFIG.hold);
+    button.advance(CONFIG.release);
+    assert_eq!(button.clicks(), usize::MAX);
+    assert_eq!(button.holds(), usize::MAX);
+}
+
+#[test]
+fn test_uncount_after_reset() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+
+    button.click_for(CONFIG.debounce);
+    button.advance(CONFIG.release);
+    assert!(button.is_clicked());
+    button.reset();
+
+    // A forced press is not counted, its glitch must not take back the click reset above
+    button.set_state(State::Down(button.clock.now()));
+    button.tick();
+    assert_eq!(button.state, State::Released);
+    assert_eq!(button.raw_clicks(), 0);
+}
+
+#[test]
+fn test_next_event() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+    assert_eq!(button.peek_event(), None);
+
+    button.pin.press_raw();
+    button.tick();
+    button.advance(CONFIG.debounce);
+    button.tick();
+    assert_eq!(button.peek_event(), Some(&ButtonEvent::Press));
+    assert_eq!(button.peek_event(), Some(&ButtonEvent::Press));
+    assert_eq!(button.next_event(), Some(ButtonEvent::Press));
+    assert_eq!(button.next_event(), None);
+
+    button.pin.release_raw();
+    button.tick();
+    button.advance(CONFIG.release);
+    assert!(button.is_settled());
+    assert!(matches!(
+        button.next_event(),
+        Some(ButtonEvent::Settled { clicks: 1, .. })
+    ));
+}
+
+#[test]
+fn test_debug_without_pin() {
+    struct Pin;
+
+    impl PinWrapper for Pin {
+        fn is_high(&mut self) -> bool {
+            false
+        }
+    }
+
+    let mut button = Button::<_, Instant>::new(Pin, CONFIG);
+    button.tick();
+    let debug = format!("{button:?}");
+    assert!(debug.starts_with("Button { state: Released, clicks: 0,"));
+    assert!(debug.ends_with(", .. }"));
+}
+
+#[test]
+fn test_hold_completed() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+
+    button.click_for(CONFIG.hold);
+    assert!(button.holds_reached(1));
+    assert!(!button.holds_reached(2));
+    assert!(button.hold_completed());
+    assert!(!button.hold_completed());
+
+    button.pin.press_raw();
+    button.tick();
+    button.advance(CONFIG.hold);
+    button.tick();
+    assert!(button.holds_reached(2));
+    assert!(!button.hold_completed());
+
+    button.pin.release_raw();
+    button.tick();
+    assert!(button.hold_completed());
+    button.advance(CONFIG.release);
+    assert_eq!(button.holds(), 2);
+}
+
+#[test]
+fn test_debounced_pin() {
+    let ms = Duration::from_millis;
+    let raw = MockPin::default();
+    let clock = MockClock::default();
+    let mut pin = Debounced::with_clock(raw.clone(), ms(5), ms(10), clock.clone());
+    assert!(!pin.is_high());
+
+    raw.press_raw();
+    assert!(!pin.is_high());
+    clock.advance(ms(4));
+    raw.release_raw();
+    assert!(!pin.is_high());
+    raw.press_raw();
+    assert!(!pin.is_high());
+    clock.advance(ms(4));
+    assert!(!pin.is_high());
+    clock.advance(ms(1));
+    assert!(pin.is_high());
+
+    raw.release_raw();
+    assert!(pin.is_high());
+    clock.advance(ms(5));
+    assert!(pin.is_high());
+    clock.advance(ms(5));
+    assert!(!pin.is_high());
+}
+
+#[test]
+fn test_debounced_pin_wrap() {
+    use std::cell::Cell;
+
+    let ticks = Cell::new(u32::MAX - 2);
+    let level = Cell::new(true);
+    let mut pin = Debounced::with_clock(FnPin(|| level.get()), 5, 5, TickClock(&ticks));
+    assert!(!pin.is_high());
+
+    for _ in 0..4 {
+        ticks.set(ticks.get().wrapping_add(1));
+        assert!(!pin.is_high());
+    }
+    ticks.set(ticks.get().wrapping_add(1));
+    assert!(pin.is_high());
+    assert_eq!(ticks.get(), 2);
+}
+
+#[test]
+fn test_events() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.pin.press_raw();
+
+    let mut events = button.events();
+    assert_eq!(events.next(), Some(None));
+    assert_eq!(events.next(), Some(None));
+
+    button.clock.advance(CONFIG.debounce);
+    assert_eq!(
+        button.events().take(3).collect::<Vec<_>>(),
+        [Some(ButtonEvent::Press), None, None]
+    );
+}
+
+#[test]
+fn test_ignore_initial_press() {
+    let config = ButtonConfig {
+        ignore_initial_press: true,
+        ..CONFIG
+    };
+    let mut button = Button::with_clock(MockPin::default(), config, MockClock::default());
+    button.pin.press_raw();
+    button.tick();
+    button.advance(CONFIG.hold);
+    button.tick();
+    assert_eq!(button.state, State::Unknown);
+
+    button.pin.release_raw();
+    button.tick();
+    button.advance(CONFIG.release);
+    assert_eq!(button.state, State::Released);
+    assert_eq!(button.clicks(), 0);
+    assert_eq!(button.holds(), 0);
+
+    button.click_for(CONFIG.debounce);
+    button.advance(CONFIG.release);
+    assert!(button.is_clicked());
+}
+
+#[test]
+fn test_active_level() {
+    struct ActiveLow(MockPin);
+
+    impl PinWrapper for ActiveLow {
+        fn is_high(&mut self) -> bool {
+            self.0.is_high()
+        }
+
+        fn active_level(&self) -> Option<bool> {
+            Some(false)
+        }
+    }
+
+    // The config is pull down, but the pin reports itself as active low.
+    let pin = MockPin::default();
+    pin.press_raw();
+    let mut button = Button::with_clock(ActiveLow(pin.clone()), CONFIG, MockClock::default());
+    button.tick();
+    assert_eq!(button.state, State::Released);
+
+    pin.release_raw();
+    button.tick();
+    button.clock.advance(CONFIG.debounce);
+    button.tick();
+    assert!(button.read_debounced());
+}
+
+#[test]
+fn test_u32_ticks() {
+    use crate::prelude::*;
+    use std::cell::Cell;
+
+    struct Counter<'a>(&'a Cell<u32>);
+
+    impl ClockSource<u32, u32> for Counter<'_> {
+        fn now(&self) -> u32 {
+            self.0.get()
+        }
+    }
+
+    let ticks = Cell::new(0);
+    let pressed = Cell::new(false);
+    let config = ButtonConfig::new(2, 10, 50, Mode::PullDown);
+    let mut button = Button::with_clock(FnPin(|| pressed.get()), config, Counter(&ticks));
+    let run = |button: &mut Button<_, u32, u32, _>, level, n| {
+        pressed.set(level);
+        for _ in 0..n {
+            ticks.set(ticks.get() + 1);
+            button.tick();
+        }
+    };
+
+    run(&mut button, false, 1);
+    run(&mut button, true, 5);
+    run(&mut button, false, 3);
+    run(&mut button, true, 60);
+    run(&mut button, false, 11);
+    assert!(button.matches(Gesture::ClicksThenHold(1)));
+    assert_eq!(button.held_time(), Some(60));
+}
+
+#[test]
+fn test_selective_reset() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+
+    button.click_for(CONFIG.debounce);
+    button.click_for(CONFIG.hold);
+    button.reset_clicks();
+    assert_eq!(button.raw_clicks(), 1);
+
+    button.advance(CONFIG.release);
+    button.reset_clicks();
+    assert_eq!(button.clicks(), 0);
+    assert_eq!(button.holds(), 1);
+    assert!(button.held_time().is_some());
+
+    button.reset_holds();
+    assert_eq!(button.holds(), 0);
+    assert_eq!(button.held_time(), None);
+}
+
+#[test]
+fn test_timing_setters() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    assert_eq!(CONFIG.validate(), Ok(()));
+
+    assert_eq!(button.set_hold(Duration::from_millis(300)), Ok(()));
+    assert_eq!(button.config().hold, Duration::from_millis(300));
+
+    assert_eq!(
+        button.set_release(Duration::from_millis(400)),
+        Err(ConfigError::ReleaseExceedsHold)
+    );
+    assert_eq!(
+        button.set_debounce(Duration::from_millis(40)),
+        Err(ConfigError::DebounceExceedsRelease)
+    );
+    assert_eq!(
+        button.set_hold(Duration::ZERO),
+        Err(ConfigError::ZeroDuration)
+    );
+    assert_eq!(button.config().release, CONFIG.release);
+    assert_eq!(button.config().debounce, CONFIG.debounce);
+    assert_eq!(
+        ConfigError::ReleaseExceedsHold.to_string(),
+        "release time is greater than hold time"
+    );
+}
+
+#[cfg(feature = "embedded_hal")]
+#[test]
+fn test_last_pin_error() {
+    use embedded_hal::digital::{ErrorKind, ErrorType, InputPin};
+
+    /// A pin failing while the mock pin is pressed.
+    struct Flaky(MockPin);
+
+    impl ErrorType for Flaky {
+        type Error = ErrorKind;
+    }
+
+    impl InputPin for Flaky {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            if PinWrapper::is_high(&mut self.0) {
+                Err(ErrorKind::Other)
+            } else {
+                Ok(true)
+            }
+        }
+
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            InputPin::is_high(self).map(|high| !high)
+        }
+    }
+
+    let pin = MockPin::default();
+    let mut button = Button::with_clock(
+        FalliblePin::new(Flaky(pin.clone())),
+        CONFIG,
+        MockClock::default(),
+    );
+    button.tick();
+    assert_eq!(button.last_pin_error(), None);
+    assert!(button.read_raw());
+
+    pin.press_raw();
+    button.tick();
+    assert_eq!(button.last_pin_error(), Some(&ErrorKind::Other));
+    assert!(!button.read_raw());
+
+    pin.release_raw();
+    button.tick();
+    assert_eq!(button.last_pin_error(), None);
+}
+
+#[test]
+fn test_hold_must_exceed() {
+    let config = ButtonConfig {
+        hold_must_exceed: Some(CONFIG.hold * 2),
+        ..CONFIG
+    };
+    let mut button = Button::with_clock(MockPin::default(), config, MockClock::default());
+    button.tick();
+
+    // In the dead zone the button is held, but not counted as a hold, the release makes it a click
+    button.pin.press_raw();
+    button.tick();
+    button.clock.advance(CONFIG.hold * 3 / 2);
+    assert_eq!(button.update(), Some(ButtonEvent::Press));
+    assert_eq!(button.update(), None);
+    assert!(button.state.is_held());
+    assert!(!button.long_press_triggered());
+    assert_eq!(button.raw_holds(), 0);
+    button.pin.release_raw();
+    assert_eq!(button.update(), Some(ButtonEvent::Release));
+    button.advance(CONFIG.release);
+    assert!(button.is_clicked());
+    assert_eq!(button.holds(), 0);
+    assert_eq!(button.held_time(), None);
+    button.reset();
+
+    // The hold starts once the threshold is exceeded
+    button.pin.press_raw();
+    button.tick();
+    button.advance(CONFIG.hold * 2);
+    button.tick();
+    assert!(button.state.is_held());
+    assert_eq!(button.raw_holds(), 0);
+    button.clock.advance(Duration::from_millis(1));
+    assert_eq!(button.update(), Some(ButtonEvent::HoldStart));
+    assert!(button.long_press_triggered());
+    assert_eq!(button.raw_holds(), 1);
+    button.pin.release_raw();
+    assert_eq!(
+        button.update(),
+        Some(ButtonEvent::HoldEnd(
+            CONFIG.hold * 2 + Duration::from_millis(1)
+        ))
+    );
+    button.advance(CONFIG.release);
+    assert_eq!(button.holds(), 1);
+    assert_eq!(button.clicks(), 0);
+}
+
+#[test]
+fn test_custom_machine() {
+    /// Press and release only, without debounce, holds or a release timeout.
+    /// Every release is a click, a new press starts a new streak.
+    struct PressRelease;
+
+    impl StateMachine for PressRelease {
+        fn step<P, I, D, C, const K: usize>(
+            button: &mut Button<P, I, D, C, K, Self>,
+            now: I,
+            pressed: bool,
+        ) where
+            P: PinWrapper,
+            I: core::ops::Sub<I, Output = D> + Clone + PartialOrd,
+            D: Clone + PartialOrd + Default,
+            C: ClockSource<I, D>,
+        {
+            let next = match button.raw_state() {
+                State::Released | State::Unknown if pressed => {
+                    button.set_clicks(0);
+                    State::Down(now)
+                }
+                State::Down(_) if pressed => State::Pressed(now),
+                State::Down(_) | State::Unknown | State::Up(_) => State::Released,
+                State::Pressed(_) if !pressed => {
+                    button.set_clicks(button.raw_clicks() + 1);
+                    State::Up(now)
+                }
+                _ => return,
+            };
+            button.set_state(next);
+        }
+    }
+
+    let button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    let mut button = button.with_machine::<PressRelease>();
+    let samples = [false, true, true, true, false, false].map(|level| (Duration::ZERO, level));
+    assert_eq!(
+        button.replay(samples).collect::<Vec<_>>(),
+        [
+            ButtonEvent::Press,
+            ButtonEvent::Release,
+            ButtonEvent::Settled {
+                clicks: 1,
+                holds: 0,
+                held: None
+            }
+        ]
+    );
+    assert_eq!(button.clicks(), 1);
+}
+
+#[test]
+fn test_state_instant() {
+    let instant = Duration::from_millis(5);
+    assert_eq!(State::Down(instant).instant(), Some(&instant));
+    assert_eq!(State::Pressed(instant).instant(), Some(&instant));
+    assert_eq!(State::Up(instant).instant(), Some(&instant));
+    assert_eq!(State::Held(instant).instant(), Some(&instant));
+    assert_eq!(State::<Duration>::Released.instant(), None);
+    assert_eq!(State::<Duration>::Unknown.instant(), None);
+}
+
+#[test]
+fn test_clicks_after_hold() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+
+    button.click_for(CONFIG.hold);
+    assert!(button.raw_state().is_up());
+    button.click_for(CONFIG.debounce);
+    button.advance(CONFIG.release);
+    assert_eq!(button.holds(), 1);
+    assert_eq!(button.clicks(), 1);
+    assert_eq!(button.clicks_before_hold(), 0);
+}
+
+#[test]
+fn test_hold_progress() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+    assert_eq!(button.hold_progress(), None);
+
+    button.pin.press_raw();
+    button.tick();
+    assert_eq!(button.hold_progress(), None);
+    button.advance(CONFIG.hold / 2);
+    assert_eq!(button.hold_progress(), Some(0.5));
+    button.advance(CONFIG.hold);
+    assert!(button.state.is_held());
+    assert_eq!(button.hold_progress(), Some(1.0));
+
+    button.pin.release_raw();
+    button.tick();
+    assert_eq!(button.hold_progress(), None);
+}
+
+#[test]
+fn test_borrowed_pin() {
+    let mut pin = FnPin(|| true);
+    let mut button = Button::with_clock(PinRef(&mut pin), CONFIG, MockClock::default());
+    button.tick();
+    assert!(button.read_raw());
+    drop(button);
+
+    assert!(pin.is_high());
+}
+
+#[test]
+fn test_debounced_level() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+    assert!(!button.debounced_level());
+
+    button.pin.press_raw();
+    button.tick();
+    assert!(!button.debounced_level());
+    button.advance(CONFIG.debounce);
+    assert!(button.debounced_level());
+    button.advance(CONFIG.hold);
+    assert!(button.debounced_level());
+
+    button.pin.release_raw();
+    button.tick();
+    assert!(button.raw_state().is_up());
+    assert!(!button.debounced_level());
+}
+
+#[test]
+fn test_state_kind() {
+    use std::collections::HashMap;
+
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    assert_eq!(button.state_kind(), StateKind::Unknown);
+    button.tick();
+
+    let mut seen = HashMap::new();
+    button.pin.press_raw();
+    button.tick();
+    *seen.entry(button.state_kind()).or_insert(0) += 1;
+    button.advance(CONFIG.debounce);
+    *seen.entry(button.state_kind()).or_insert(0) += 1;
+    button.advance(CONFIG.debounce);
+    *seen.entry(button.state_kind()).or_insert(0) += 1;
+
+    assert_eq!(seen.get(&StateKind::Down), Some(&1));
+    assert_eq!(seen.get(&StateKind::Pressed), Some(&2));
+    assert_eq!(button.raw_state().kind(), StateKind::Pressed);
+}
+
+#[test]
+fn test_report() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+
+    button.click_for(CONFIG.debounce);
+    button.click_for(CONFIG.hold);
+    button.pin.press_raw();
+    button.tick();
+    button.advance(CONFIG.debounce);
+    button.advance(CONFIG.hold);
+    assert_eq!(
+        button.report(),
+        GestureReport {
+            clicks: 0,
+            holds: 0,
+            held_time: None,
+            current_hold: Some(CONFIG.hold + CONFIG.debounce),
+        }
+    );
+
+    button.pin.release_raw();
+    button.tick();
+    button.advance(CONFIG.release);
+    assert_eq!(
+        button.report(),
+        GestureReport {
+            clicks: 1,
+            holds: 2,
+            held_time: Some(CONFIG.hold + CONFIG.debounce),
+            current_hold: None,
+        }
+    );
+}
+
+#[test]
+fn test_diagnostics() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+
+    button.click_for(CONFIG.debounce);
+    button.clock.advance(CONFIG.release / 2);
+    assert_eq!(
+        button.diagnostics(),
+        Diagnostics {
+            clicks: 0,
+            holds: 0,
+            raw_clicks: 1,
+            raw_holds: 0,
+            state: StateKind::Up,
+            time_in_state: Some(CONFIG.release / 2),
+        }
+    );
+
+    button.advance(CONFIG.release);
+    assert_eq!(
+        button.diagnostics(),
+        Diagnostics {
+            clicks: 1,
+            holds: 0,
+            raw_clicks: 1,
+            raw_holds: 0,
+            state: StateKind::Released,
+            time_in_state: None,
+        }
+    );
+}
+
+#[test]
+fn test_was_held() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+
+    button.click_for(CONFIG.debounce);
+    button.advance(CONFIG.release);
+    assert!(button.is_clicked());
+    assert!(!button.was_held());
+    button.reset();
+
+    button.click_for(CONFIG.hold);
+    assert!(!button.was_held());
+    button.advance(CONFIG.release);
+    assert!(button.was_held());
+    assert!(!button.is_clicked());
+    button.reset();
+    assert!(!button.was_held());
+}
+
+#[test]
+fn test_asymmetric_debounce() {
+    let config = ButtonConfig {
+        debounce: Duration::from_millis(2),
+        release_debounce: Some(Duration::from_millis(10)),
+        ..CONFIG
+    };
+    let mut button = Button::with_clock(MockPin::default(), config, MockClock::default());
+    button.tick();
+
+    // short debounce on press
+    button.pin.press_raw();
+    button.tick();
+    button.advance(Duration::from_millis(1));
+    assert!(button.state.is_down());
+    button.advance(Duration::from_millis(1));
+    assert!(button.state.is_pressed());
+
+    // long bounce on release
+    button.pin.release_raw();
+    button.tick();
+    button.advance(Duration::from_millis(5));
+    assert!(button.state.is_pressed());
+    button.pin.press_raw();
+    button.tick();
+    button.pin.release_raw();
+    button.tick();
+    button.advance(Duration::from_millis(10));
+    assert!(button.state.is_up());
+
+    button.advance(CONFIG.release);
+    assert_eq!(button.clicks(), 1);
+}
+
+#[test]
+fn test_force_release() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.force_release();
+    assert_eq!(button.state, State::Unknown);
+    button.tick();
+
+    button.click_for(CONFIG.debounce);
+    button.pin.press_raw();
+    button.tick();
+    button.advance(CONFIG.debounce);
+    button.advance(CONFIG.hold);
+    button.clock.advance(CONFIG.hold);
+    button.force_release();
+    assert_eq!(button.state, State::Released);
+    assert_eq!(button.clicks(), 1);
+    assert_eq!(button.holds(), 1);
+    assert_eq!(button.held_time(), Some(CONFIG.hold * 2 + CONFIG.debounce));
+    assert!(button.hold_completed());
+    button.reset();
+
+    button.pin.release_raw();
+    button.tick();
+    button.pin.press_raw();
+    button.tick();
+    button.force_release();
+    assert_eq!(button.clicks(), 0);
+}
+
+#[test]
+fn test_snapshot() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+    button.click_for(CONFIG.debounce);
+    button.click_for(CONFIG.hold);
+    button.advance(CONFIG.release);
+
+    let snapshot = button.snapshot();
+    assert_eq!(snapshot.state, StateKind::Released);
+    let restored = Button::<_, Instant>::from_snapshot(MockPin::default(), snapshot);
+    assert_eq!(restored.state, State::Released);
+    assert_eq!(restored.clicks(), 1);
+    assert_eq!(restored.holds(), 1);
+    assert_eq!(restored.config(), &CONFIG);
+
+    button.pin.press_raw();
+    button.tick();
+    let restored = Button::<_, Instant>::from_snapshot(MockPin::default(), button.snapshot());
+    assert_eq!(restored.state, State::Unknown);
+}
+
+#[test]
+fn test_clicks_and_holds() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+    assert_eq!(button.clicks_and_holds(), None);
+
+    button.click_for(CONFIG.debounce);
+    button.click_for(CONFIG.debounce);
+    button.click_for(CONFIG.hold);
+    button.click_for(CONFIG.hold);
+    assert_eq!(button.clicks_and_holds(), None);
+    button.advance(CONFIG.release);
+    assert_eq!(button.clicks_and_holds(), Some((2, 2)));
+
+    button.reset();
+    assert_eq!(button.clicks_and_holds(), None);
+}
+
+#[test]
+fn test_tick_if_changed() {
+    let config = ButtonConfig {
+        auto_reset: true,
+        ..CONFIG
+    };
+    let mut button = Button::with_clock(MockPin::default(), config, MockClock::default());
+    button.tick_if_changed();
+    assert_eq!(button.state, State::Released);
+    button.tick_if_changed();
+    assert_eq!(button.state, State::Released);
+
+    button.pin.press_raw();
+    button.tick_if_changed();
+    button.clock.advance(CONFIG.debounce);
+    button.tick_if_changed();
+    assert!(button.state.is_pressed());
+    button.clock.advance(CONFIG.hold);
+    button.tick_if_changed();
+    assert!(button.state.is_held());
+    button.tick_if_changed();
+    assert!(button.state.is_held());
+
+    button.pin.release_raw();
+    button.tick_if_changed();
+    button.clock.advance(CONFIG.release);
+    button.tick_if_changed();
+    assert!(button.is_settled());
+    assert_eq!(button.holds(), 1);
+
+    // the finished gesture is still reset on the next tick
+    button.tick_if_changed();
+    assert_eq!(button.holds(), 0);
+}
+
+#[test]
+fn test_tick_if_changed_hold_bounce() {
+    let ms = Duration::from_millis;
+    let config = ButtonConfig {
+        release_debounce: Some(ms(5)),
+        ..CONFIG
+    };
+    let mut button = Button::with_clock(MockPin::default(), config, MockClock::default());
+    button.tick_if_changed();
+
+    button.pin.press_raw();
+    button.tick_if_changed();
+    button.clock.advance(CONFIG.hold);
+    button.tick_if_changed();
+    button.tick_if_changed();
+    assert!(button.state.is_held());
+
+    // a bounce during the hold
+    button.pin.release_raw();
+    button.clock.advance(ms(1));
+    button.tick_if_changed();
+    button.pin.press_raw();
+    button.clock.advance(ms(1));
+    button.tick_if_changed();
+    assert!(button.state.is_held());
+
+    // the final release is still debounced
+    button.clock.advance(ms(800));
+    button.pin.release_raw();
+    button.tick_if_changed();
+    assert!(button.state.is_held());
+    button.clock.advance(ms(5));
+    button.tick_if_changed();
+    assert!(matches!(button.state, State::Up(_)));
+}
+
+#[test]
+fn test_public_mock_pin() {
+    let pin = testing::MockPin::new(Mode::PullUp);
+    let config = ButtonConfig {
+        mode: Mode::PullUp,
+        ..CONFIG
+    };
+    let clock = MockClock::default();
+    let mut button = Button::with_clock(pin.clone(), config, clock.clone());
+    button.tick();
+    assert_eq!(button.state, State::Released);
+
+    pin.press();
+    button.tick();
+    clock.advance(CONFIG.debounce);
+    button.tick();
+    assert!(button.state.is_pressed());
+
+    pin.release();
+    button.tick();
+    clock.advance(CONFIG.release);
+    button.tick();
+    assert!(button.is_clicked());
+
+    // A button configured for the other mode misreads the pin
+    let mut mismatched = Button::with_clock(pin, CONFIG, clock);
+    mismatched.tick();
+    assert!(matches!(mismatched.state, State::Down(_)));
+}
+
+#[test]
+fn test_map_duration() {
+    let config = ButtonConfig {
+        repeat_accel: Some((3, Duration::from_millis(20))),
+        ..CONFIG
+    }
+    .map_duration(|d| d.as_millis() as u32);
+    assert_eq!(config.debounce, 0);
+    assert_eq!(config.release, 30);
+    assert_eq!(config.hold, 500);
+    assert_eq!(config.repeat_accel, Some((3, 20)));
+    assert_eq!(config.mode, CONFIG.mode);
+}
+
+#[test]
+fn test_long_press_triggered() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+
+    button.pin.press_raw();
+    button.tick();
+    button.advance(CONFIG.debounce);
+    assert!(!button.long_press_triggered());
+    button.advance(CONFIG.hold);
+    assert!(button.state.is_held());
+    assert!(button.long_press_triggered());
+    assert!(!button.long_press_triggered());
+    button.advance(CONFIG.hold);
+    assert!(!button.long_press_triggered());
+
+    // not taken during the hold
+    button.pin.release_raw();
+    button.tick();
+    button.pin.press_raw();
+    button.tick();
+    button.advance(CONFIG.debounce);
+    button.advance(CONFIG.hold);
+    button.pin.release_raw();
+    button.tick();
+    assert!(!button.long_press_triggered());
+}
+
+#[test]
+fn test_tick_changed() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    assert!(button.tick_changed());
+    assert!(!button.tick_changed());
+
+    button.pin.press_raw();
+    assert!(button.tick_changed());
+    button.clock.advance(CONFIG.debounce / 2);
+    assert!(!button.tick_changed());
+    button.clock.advance(CONFIG.debounce);
+    assert!(button.tick_changed());
+    assert!(!button.tick_changed());
+}
+
+#[test]
+fn test_click_ceiling() {
+    let config = ButtonConfig {
+        click_ceiling: Some(3),
+        ..CONFIG
+    };
+    let mut button = Button::with_clock(MockPin::default(), config, MockClock::default());
+    button.tick();
+
+    for _ in 0..5 {
+        button.click_for(CONFIG.debounce);
+    }
+    assert_eq!(button.raw_clicks(), 5);
+    button.advance(CONFIG.release);
+    assert_eq!(
+        button.peek_event(),
+        Some(&ButtonEvent::Settled {
+            clicks: 3,
+            holds: 0,
+            held: None
+        })
+    );
+    assert_eq!(button.clicks(), 3);
+    assert!(button.is_triple_clicked());
+}
+
+#[cfg(feature = "embedded_hal")]
+#[test]
+fn test_output_pin_readback() {
+    use embedded_hal::digital::{ErrorType, OutputPin, StatefulOutputPin};
+
+    /// An open-drain pin reading back the shared mock pin.
+    struct OpenDrain(MockPin);
+
+    impl ErrorType for OpenDrain {
+        type Error = core::convert::Infallible;
+    }
+
+    impl OutputPin for OpenDrain {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0.release_raw();
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0.press_raw();
+            Ok(())
+        }
+    }
+
+    impl StatefulOutputPin for OpenDrain {
+        fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+            Ok(PinWrapper::is_high(&mut self.0))
+        }
+
+        fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+            self.is_set_high().map(|high| !high)
+        }
+    }
+
+    let pin = MockPin::default();
+    let mut button = Button::with_clock(
+        OutputPinReadback(OpenDrain(pin.clone())),
+        CONFIG,
+        MockClock::default(),
+    );
+    button.tick();
+    assert!(!button.read_raw());
+
+    pin.press_raw();
+    assert!(button.read_raw());
+}
+
+#[test]
+fn test_consume() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+
+    button.click_for(CONFIG.debounce);
+    button.click_for(CONFIG.debounce);
+    button.click_for(CONFIG.hold);
+    assert_eq!(button.consume_click(), None);
+    button.advance(CONFIG.release);
+
+    assert_eq!(button.consume_click(), Some(2));
+    assert_eq!(button.consume_click(), None);
+    assert_eq!(button.consume_hold(), Some(1));
+    assert_eq!(button.consume_hold(), None);
+    assert_eq!(button.held_time(), None);
+}
+
+#[test]
+fn test_max_tick_gap() {
+    let config = ButtonConfig {
+        max_tick_gap: Some(Duration::from_millis(100)),
+        ..CONFIG
+    };
+    let mut button = Button::with_clock(MockPin::default(), config, MockClock::default());
+    button.tick();
+
+    button.click_for(CONFIG.debounce);
+    button.pin.press_raw();
+    button.tick();
+    button.advance(CONFIG.debounce);
+    assert!(button.state.is_pressed());
+
+    // the loop was blocked, the release and the timeouts were missed
+    button.pin.release_raw();
+    button.clock.advance(Duration::from_secs(2));
+    assert_eq!(button.events().next(), Some(None));
+    assert_eq!(button.state, State::Unknown);
+    assert_eq!(button.raw_clicks(), 0);
+    assert_eq!(button.raw_holds(), 0);
+
+    button.tick();
+    assert_eq!(button.state, State::Released);
+    assert_eq!(button.clicks(), 0);
+}
+
+#[test]
+fn test_from_config_ref() {
+    let config = ButtonConfig {
+        max_clicks: Some(2),
+        ..CONFIG
+    };
+    let buttons: [Button<MockPin, Instant>; 2] =
+        std::array::from_fn(|_| Button::from_config_ref(MockPin::default(), &config));
+    assert!(buttons.iter().all(|button| button.config() == &config));
+}
+
+#[test]
+fn test_with_name() {
+    let button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    assert_eq!(button.name(), "");
+
+    let button = button.with_name("enter");
+    assert_eq!(button.name(), "enter");
+    assert!(format!("{button:?}").starts_with("Button { name: \"enter\", state: Unknown,"));
+}
+
+#[test]
+fn test_taps_during_hold() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+
+    button.click_for(CONFIG.debounce);
+    assert_eq!(button.taps_during_hold(), 0);
+    button.click_for(CONFIG.hold);
+    assert_eq!(button.taps_during_hold(), 0);
+    button.click_for(CONFIG.debounce);
+    button.click_for(CONFIG.debounce);
+    assert_eq!(button.taps_during_hold(), 2);
+
+    button.advance(CONFIG.release);
+    assert_eq!(button.taps_during_hold(), 2);
+    assert_eq!(button.clicks(), 3);
+    button.reset();
+    assert_eq!(button.taps_during_hold(), 0);
+}
+
+#[test]
+fn test_last_change() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    assert_eq!(button.last_change(), None);
+    button.tick();
+    assert_eq!(
+        button.last_change(),
+        Some((StateKind::Unknown, StateKind::Released))
+    );
+    button.tick();
+    assert_eq!(button.last_change(), None);
+
+    button.pin.press_raw();
+    button.tick();
+    assert_eq!(
+        button.last_change(),
+        Some((StateKind::Released, StateKind::Down))
+    );
+    button.advance(CONFIG.debounce);
+    assert_eq!(
+        button.last_change(),
+        Some((StateKind::Down, StateKind::Pressed))
+    );
+    button.tick();
+    assert_eq!(button.last_change(), None);
+}
+
+#[test]
+fn test_is_bouncing() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+    assert!(!button.is_bouncing());
+
+    button.pin.press_raw();
+    button.tick();
+    assert!(button.is_bouncing());
+    button.clock.advance(CONFIG.debounce);
+    assert!(button.state.is_down());
+    assert!(!button.is_bouncing());
+    button.tick();
+    assert!(!button.is_bouncing());
+}
+
+#[test]
+fn test_scaled_config() {
+    let config = ButtonConfig::scaled(Duration::from_millis(150), 150, 3, Mode::PullDown);
+    assert_eq!(config.debounce, Duration::from_millis(1));
+    assert_eq!(config.release, Duration::from_millis(150));
+    assert_eq!(config.hold, Duration::from_millis(450));
+    assert_eq!(config.validate(), Ok(()));
+
+    let config = ButtonConfig::<u32>::scaled(100, 10, 4, Mode::PullUp);
+    assert_eq!(
+        (config.debounce, config.release, config.hold),
+        (10, 100, 400)
+    );
+}
+
+#[test]
+fn test_latched() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.set_latched(true);
+    button.tick();
+
+    button.click_for(CONFIG.debounce);
+    assert!(!button.latched());
+    button.click_for(CONFIG.hold);
+    assert!(!button.latched());
+    button.click_for(CONFIG.debounce);
+    assert!(button.latched());
+}
+
+#[test]
+fn test_elapsed_since() {
+    assert_eq!(TickCount(5).elapsed_since(&TickCount(12)), 7);
+    assert_eq!(TickCount(u32::MAX).elapsed_since(&TickCount(1)), 2);
+
+    let start = Instant::now();
+    let now = start + Duration::from_millis(3);
+    assert_eq!(start.elapsed_since(&now), Duration::from_millis(3));
+    assert_eq!(
+        ClockSource::<Instant>::checked_elapsed_since(&GlobalClock, &start, &now),
+        Some(Duration::from_millis(3))
+    );
+    assert_eq!(
+        ClockSource::<Instant>::checked_elapsed_since(&GlobalClock, &now, &start),
+        None
+    );
+
+    // A clock behind the instant saturates at zero
+    let clock = testing::SimClock(Duration::from_millis(5));
+    assert_eq!(clock.elapsed(&Duration::from_millis(10)), Duration::ZERO);
+    assert_eq!(
+        clock.elapsed(&Duration::from_millis(2)),
+        Duration::from_millis(3)
+    );
+}
+
+#[test]
+fn test_for_switch() {
+    let config = ButtonConfig::<Duration>::for_switch(SwitchType::Membrane);
+    assert_eq!(config.debounce, Duration::from_millis(10));
+    assert_eq!(config.hold, Duration::from_millis(600));
+    assert_eq!(config.mode, Mode::PullUp);
+
+    for switch in [
+        SwitchType::Tactile,
+        SwitchType::Membrane,
+        SwitchType::Reed,
+        SwitchType::Capacitive,
+    ] {
+        assert_eq!(
+            ButtonConfig::<Duration>::for_switch(switch).validate(),
+            Ok(())
+        );
+    }
+}
+
+#[test]
+fn test_elapsed_in_state() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    assert_eq!(button.elapsed_in_state(), None);
+    button.tick();
+    assert_eq!(button.elapsed_in_state(), None);
+
+    button.pin.press_raw();
+    button.tick();
+    button.clock.advance(CONFIG.debounce / 2);
+    assert_eq!(button.elapsed_in_state(), Some(CONFIG.debounce / 2));
+
+    button.pin.release_raw();
+    button.click_for(CONFIG.debounce);
+    button.clock.advance(CONFIG.release / 2);
+    assert!(button.state.is_up());
+    assert_eq!(button.elapsed_in_state(), Some(CONFIG.release / 2));
+}
+
+#[test]
+fn test_click_window_remaining() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.tick();
+    assert_eq!(button.click_window_remaining(), None);
+
+    button.pin.press_raw();
+    button.tick();
+    assert_eq!(button.click_window_remaining(), None);
+
+    button.pin.release_raw();
+    button.click_for(CONFIG.debounce);
+    assert!(button.state.is_up());
+    assert_eq!(button.click_window_remaining(), Some(CONFIG.release));
+
+    button.clock.advance(CONFIG.release / 3);
+    assert_eq!(
+        button.click_window_remaining(),
+        Some(CONFIG.release - CONFIG.release / 3)
+    );
+
+    button.clock.advance(CONFIG.release);
+    assert_eq!(button.click_window_remaining(), Some(Duration::ZERO));
+}
+
+#[test]
+fn test_names() {
+    assert_eq!(State::Held(Duration::ZERO).name(), "Held");
+    assert_eq!(State::<Duration>::Released.name(), "Released");
+    assert_eq!(Mode::PullDown.name(), "PullDown");
+}
+
+#[test]
+fn test_autofire() {
+    let config = ButtonConfig {
+        autofire_interval: Some(Duration::from_millis(10)),
+        ..CONFIG
+    };
+    let mut button = Button::with_clock(MockPin::default(), config, MockClock::default());
+    button.tick();
+
+    // 1kHz loop
+    let run = |button: &mut Button<_, _, _, _>, ms| {
+        let mut fired = 0;
+        for _ in 0..ms {
+            button.advance(Duration::from_millis(1));
+            fired += usize::from(button.fire());
+        }
+        fired
+    };
+
+    button.pin.press_raw();
+    button.tick();
+    assert_eq!(run(&mut button, 35), 4);
+    button.pin.release_raw();
+    assert_eq!(run(&mut button, 20), 0);
+
+    button.pin.press_raw();
+    button.tick();
+    assert_eq!(run(&mut button, 5), 1);
+
+    // The frozen press does not fire
+    button.pause();
+    assert_eq!(run(&mut button, 20), 0);
+    button.resume();
+}
+
+#[test]
+fn test_float_seconds() {
+    /// Time is fed by samples only.
+    struct NoClock;
+
+    impl ClockSource<f64, f64> for NoClock {
+        fn now(&self) -> f64 {
+            0.0
+        }
+    }
+
+    let config = ButtonConfig::new(0.001, 0.15, 0.5, Mode::PullDown);
+    assert_eq!(config.validate(), Ok(()));
+    let mut button = Button::with_clock(FnPin(|| false), config, NoClock);
+    button.tick_sampled(&[
+        (0.0, false),
+        (0.1, true),
+        (0.2, true),
+        (0.25, false),
+        (0.5, false),
+    ]);
+    assert!(button.is_clicked());
+
+    button.reset();
+    button.tick_sampled(&[(1.0, true), (1.01, true), (1.6, true), (1.7, false)]);
+    assert_eq!(button.raw_holds(), 1);
+    assert!(button.held_time().is_none());
+    button.tick_sampled(&[(2.0, false)]);
+    assert!((button.held_time().unwrap() - 0.7).abs() < 1e-9);
+}
+
+#[test]
+fn test_min_click_interval() {
+    let config = ButtonConfig {
+        min_click_interval: Some(CONFIG.release / 2),
+        ..CONFIG
+    };
+    let mut button = Button::with_clock(MockPin::default(), config, MockClock::default());
+    button.tick();
+
+    button.click_for(CONFIG.debounce);
+    button.advance(CONFIG.release / 4);
+    button.click_for(CONFIG.debounce);
+    button.advance(CONFIG.release);
+    assert_eq!(button.clicks(), 1);
+
+    button.reset();
+    button.click_for(CONFIG.debounce);
+    button.advance(CONFIG.release / 2);
+    button.click_for(CONFIG.debounce);
+    button.advance(CONFIG.release);
+    assert_eq!(button.clicks(), 2);
+}
+
+#[test]
+fn test_uptime() {
+    let mut button = Button::with_clock(MockPin::default(), CONFIG, MockClock::default());
+    button.clock.advance(CONFIG.release);
+    assert_eq!(button.uptime(), Duration::ZERO);
+    button.tick();
+    button.advance(CONFIG.hold);
+    assert_eq!(button.uptime(), CONFIG.hold);
+
+    let button = Button::<_, Instant>::start(MockPin::default(), CONFIG);
+    sleep(CONFIG.release);
+    assert!(button.uptime() >= CONFIG.release);
+}